@@ -1,7 +1,10 @@
 #![warn(missing_docs)]
 
 use divrem::DivFloor;
-use std::{array, collections::HashMap};
+use std::{
+    array,
+    collections::{HashMap, VecDeque},
+};
 
 /// Chunks in the ChunkMap are always `CHUNK_SIZE x CHUNK_SIZE`.
 ///
@@ -12,6 +15,10 @@ const CHUNK_SIZE_I32: i32 = CHUNK_SIZE as i32;
 
 type Chunk<T> = [[T; CHUNK_SIZE]; CHUNK_SIZE];
 
+fn empty_chunk<T>() -> Chunk<Option<T>> {
+    array::from_fn(|_| array::from_fn(|_| None))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ChunkCoordinate {
     x: i32,
@@ -20,26 +27,31 @@ struct ChunkCoordinate {
 
 impl ChunkCoordinate {
     fn get_from_coordinates(x: i32, y: i32) -> ChunkCoordinate {
+        // Rounding down to a multiple of `CHUNK_SIZE` can't overflow, even
+        // for `i32::MIN` and `i32::MAX`, since `i32::MIN` is itself a
+        // multiple of `CHUNK_SIZE`.
         ChunkCoordinate {
             x: DivFloor::div_floor(x, CHUNK_SIZE_I32) * CHUNK_SIZE_I32,
             y: DivFloor::div_floor(y, CHUNK_SIZE_I32) * CHUNK_SIZE_I32,
         }
     }
 
-    fn x_offset(&self, x: i32) -> usize {
-        let offset = x - self.x;
-        if (0..CHUNK_SIZE_I32).contains(&offset) {
-            return offset as usize;
+    /// How far `x` is from the left of this chunk, or `None` if `x`
+    /// isn't in this chunk.
+    fn x_offset(&self, x: i32) -> Option<usize> {
+        match x.checked_sub(self.x)? {
+            offset @ 0..CHUNK_SIZE_I32 => Some(offset as usize),
+            _ => None,
         }
-        panic!("Cannot find x_offset within this chunk!")
     }
 
-    fn y_offset(&self, y: i32) -> usize {
-        let offset = y - self.y;
-        if (0..CHUNK_SIZE_I32).contains(&offset) {
-            return offset as usize;
+    /// How far `y` is from the top of this chunk, or `None` if `y`
+    /// isn't in this chunk.
+    fn y_offset(&self, y: i32) -> Option<usize> {
+        match y.checked_sub(self.y)? {
+            offset @ 0..CHUNK_SIZE_I32 => Some(offset as usize),
+            _ => None,
         }
-        panic!("Cannot find y_offset within this chunk!")
     }
 }
 
@@ -62,26 +74,144 @@ impl<T: Clone> ChunkMap<T> {
         }
     }
 
+    /// Returns how wide (and tall) each chunk is. Every chunk's top-left
+    /// corner is at coordinates which are multiples of this; so data kept
+    /// in squares of the same size (see [`ChunkMap::chunk_origin_of`])
+    /// lines up with the map's chunks.
+    pub fn chunk_size() -> usize {
+        CHUNK_SIZE
+    }
+
+    /// Returns the top-left corner of the chunk containing `(x, y)`.
+    ///
+    /// ```rust
+    /// use termgame::ChunkMap;
+    /// let size = ChunkMap::<char>::chunk_size() as i32;
+    /// assert_eq!(ChunkMap::<char>::chunk_origin_of(size + 1, -1), (size, -size));
+    /// ```
+    pub fn chunk_origin_of(x: i32, y: i32) -> (i32, i32) {
+        let coord = ChunkCoordinate::get_from_coordinates(x, y);
+        (coord.x, coord.y)
+    }
+
     /// Returns a mutable reference to an [`Option<T>`], which
     /// is the slot for `(x, y)`. This is always a mutating operation,
     /// as even if the chunk for `(x, y)` has not been created yet; this
     /// must create it.
     fn get_slot(&mut self, x: i32, y: i32) -> &mut Option<T> {
         let coord = ChunkCoordinate::get_from_coordinates(x, y);
-        &mut self.map.entry(coord).or_insert_with(
-            || Self::empty_chunk()
-        )[coord.x_offset(x)][coord.y_offset(y)]
-    }
-
-    fn empty_chunk() -> [[Option<T>; CHUNK_SIZE]; CHUNK_SIZE] {
-        array::from_fn(|_| array::from_fn(|_| None))
+        // `coord` is the chunk containing `(x, y)`, so the offsets are
+        // just how far `(x, y)` is past a multiple of `CHUNK_SIZE`.
+        let x_offset = x.rem_euclid(CHUNK_SIZE_I32) as usize;
+        let y_offset = y.rem_euclid(CHUNK_SIZE_I32) as usize;
+        &mut self.map.entry(coord).or_insert_with(empty_chunk)[x_offset][y_offset]
     }
 
     /// Returns an Optional reference to the `T` at `(x, y)` if there
     /// is one.
     pub fn get(&self, x: i32, y: i32) -> Option<&T> {
         let coord = ChunkCoordinate::get_from_coordinates(x, y);
-        self.map.get(&coord)?[coord.x_offset(x)][coord.y_offset(y)].as_ref()
+        self.map.get(&coord)?[coord.x_offset(x)?][coord.y_offset(y)?].as_ref()
+    }
+
+    /// Returns an iterator over every `(x, y, value)` in the map,
+    /// in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, i32, &T)> {
+        self.map.iter().flat_map(|(coord, chunk)| {
+            chunk
+                .iter()
+                .enumerate()
+                .flat_map(move |(x_offset, column)| {
+                    column
+                        .iter()
+                        .enumerate()
+                        .filter_map(move |(y_offset, val)| {
+                            let val = val.as_ref()?;
+                            Some((coord.x + x_offset as i32, coord.y + y_offset as i32, val))
+                        })
+                })
+        })
+    }
+
+    /// Returns an iterator over every chunk in the map, in no particular
+    /// order, as the `(x, y)` of its top-left corner (see
+    /// [`ChunkMap::chunk_origin_of`]) and the chunk itself. This is faster
+    /// than [`ChunkMap::iter`] for looking at large parts of the map.
+    ///
+    /// Chunks are stored column by column, so `chunk[col][row]` is the
+    /// value at `(x + col, y + row)`. Chunks may be entirely empty.
+    ///
+    /// ```rust
+    /// use termgame::ChunkMap;
+    /// let mut map = ChunkMap::new();
+    /// map.insert(-1, 2, 'a');
+    /// let (x, y, chunk) = map.chunks().next().unwrap();
+    /// assert_eq!(chunk[(-1 - x) as usize][(2 - y) as usize], Some('a'));
+    /// ```
+    pub fn chunks(
+        &self,
+    ) -> impl Iterator<Item = (i32, i32, &[[Option<T>; CHUNK_SIZE]; CHUNK_SIZE])> {
+        self.map
+            .iter()
+            .map(|(coord, chunk)| (coord.x, coord.y, chunk))
+    }
+
+    /// Returns a copy of every `(x, y, value)` in the map, sorted by row
+    /// and then by column (that is, by `(y, x)`). Unlike [`ChunkMap::iter`],
+    /// the order is always the same, so the result can be compared against
+    /// an expected list, or written out for a golden-file test.
+    pub fn cells(&self) -> Vec<(i32, i32, T)> {
+        let mut cells: Vec<_> = self.iter().map(|(x, y, val)| (x, y, val.clone())).collect();
+        cells.sort_by_key(|&(x, y, _)| (y, x));
+        cells
+    }
+
+    /// Copies the `w` by `h` rectangle with its top-left corner at `(x, y)`
+    /// into a grid, so that `grid[row][col]` is the value at
+    /// `(x + col, y + row)`. This is useful for running grid algorithms
+    /// (like pathfinding) on part of the map.
+    ///
+    /// Empty cells (and cells past the edge of the map) are `None`.
+    pub fn region_to_vec(&self, x: i32, y: i32, w: usize, h: usize) -> Vec<Vec<Option<T>>> {
+        let offset = |start: i32, by: usize| i32::try_from(by).ok()?.checked_add(start);
+        (0..h)
+            .map(|row| {
+                (0..w)
+                    .map(|col| self.get(offset(x, col)?, offset(y, row)?).cloned())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Counts how many cells in the `w` by `h` rectangle with its top-left
+    /// corner at `(x, y)` have something in them. Only the chunks which
+    /// overlap the rectangle are looked at, so this is much faster than
+    /// calling [`ChunkMap::get`] for every cell.
+    ///
+    /// ```rust
+    /// use termgame::ChunkMap;
+    /// let mut board = ChunkMap::<bool>::new();
+    /// board.insert(0, 0, true);
+    /// board.insert(9, 9, true);
+    /// board.insert(10, 10, true);
+    /// assert_eq!(board.count_in_region(0, 0, 10, 10), 2);
+    /// ```
+    pub fn count_in_region(&self, x: i32, y: i32, w: u32, h: u32) -> usize {
+        let (left, top) = (i64::from(x), i64::from(y));
+        let (right, bottom) = (left + i64::from(w), top + i64::from(h));
+        let size = CHUNK_SIZE_I32 as i64;
+        self.map
+            .iter()
+            .map(|(coord, chunk)| {
+                let (chunk_x, chunk_y) = (i64::from(coord.x), i64::from(coord.y));
+                // The part of the rectangle inside this chunk, as offsets.
+                let cols = (left.max(chunk_x) - chunk_x)..(right.min(chunk_x + size) - chunk_x);
+                let rows = (top.max(chunk_y) - chunk_y)..(bottom.min(chunk_y + size) - chunk_y);
+                cols.flat_map(|col| rows.clone().map(move |row| (col, row)))
+                    .filter(|&(col, row)| chunk[col as usize][row as usize].is_some())
+                    .count()
+            })
+            .sum()
     }
 
     /// Removes the `T` at `(x, y)` if there was one, and returns
@@ -92,7 +222,7 @@ impl<T: Clone> ChunkMap<T> {
         let coord = ChunkCoordinate::get_from_coordinates(x, y);
         // If the chunk doesn't exist, there can't be anything to remove.
         let chunk = self.map.get_mut(&coord)?;
-        let value = &mut chunk[coord.x_offset(x)][coord.y_offset(y)];
+        let value = &mut chunk[coord.x_offset(x)?][coord.y_offset(y)?];
         value.take()
     }
 
@@ -100,12 +230,289 @@ impl<T: Clone> ChunkMap<T> {
     pub fn insert(&mut self, x: i32, y: i32, val: T) {
         *self.get_slot(x, y) = Some(val);
     }
+
+    /// Inserts `val` at `(x, y)`, like [`ChunkMap::insert`].
+    ///
+    /// Returns `true` if a new chunk had to be created to hold it; that is,
+    /// if nothing within the same `32` by `32` block as `(x, y)` had been
+    /// inserted before. This can be used to notice writes far away from
+    /// everything else, which are often a mistake in the coordinates.
+    ///
+    /// ```rust
+    /// use termgame::ChunkMap;
+    /// let mut map = ChunkMap::<char>::new();
+    /// assert!(map.insert_reporting_new_chunk(0, 0, 'a'));
+    /// assert!(!map.insert_reporting_new_chunk(1, 0, 'b'));
+    /// assert!(map.insert_reporting_new_chunk(1_000_000, 1_000_000, 'c'));
+    /// ```
+    pub fn insert_reporting_new_chunk(&mut self, x: i32, y: i32, val: T) -> bool {
+        let new_chunk = !self
+            .map
+            .contains_key(&ChunkCoordinate::get_from_coordinates(x, y));
+        self.insert(x, y, val);
+        new_chunk
+    }
+
+    /// Returns a mutable reference to the `T` at `(x, y)`. If there
+    /// is nothing there, the result of `f` is inserted first.
+    ///
+    /// ```rust
+    /// use termgame::ChunkMap;
+    /// let mut visits = ChunkMap::<u32>::new();
+    /// *visits.get_or_insert_with(3, 4, || 0) += 1;
+    /// *visits.get_or_insert_with(3, 4, || 0) += 1;
+    /// assert_eq!(visits.get(3, 4), Some(&2));
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce() -> T>(&mut self, x: i32, y: i32, f: F) -> &mut T {
+        self.get_slot(x, y).get_or_insert_with(f)
+    }
+
+    /// Returns the [`Entry`] for `(x, y)`, which can be used to look at,
+    /// change, or insert the value there while only looking it up once.
+    ///
+    /// ```rust
+    /// use termgame::ChunkMap;
+    /// let mut counts = ChunkMap::<u32>::new();
+    /// counts.entry(1, 2).and_modify(|count| *count += 1).or_insert(1);
+    /// counts.entry(1, 2).and_modify(|count| *count += 1).or_insert(1);
+    /// assert_eq!(counts.get(1, 2), Some(&2));
+    /// ```
+    pub fn entry(&mut self, x: i32, y: i32) -> Entry<'_, T> {
+        let coord = ChunkCoordinate::get_from_coordinates(x, y);
+        let x_offset = x.rem_euclid(CHUNK_SIZE_I32) as usize;
+        let y_offset = y.rem_euclid(CHUNK_SIZE_I32) as usize;
+        // A vacant entry doesn't create its chunk until something is
+        // inserted, so that looking at empty space doesn't use memory.
+        let occupied = self
+            .map
+            .get(&coord)
+            .is_some_and(|chunk| chunk[x_offset][y_offset].is_some());
+        if !occupied {
+            return Entry::Vacant(VacantEntry {
+                map: &mut self.map,
+                coord,
+                x_offset,
+                y_offset,
+            });
+        }
+        let chunk = self
+            .map
+            .get_mut(&coord)
+            .expect("occupied cells have a chunk");
+        Entry::Occupied(OccupiedEntry {
+            slot: &mut chunk[x_offset][y_offset],
+        })
+    }
+
+    /// Inserts or removes many values at once. Each item of `cells` is the
+    /// `(x, y)` to change, and either `Some` value to insert there, or
+    /// `None` to remove what was there. Consecutive changes in the same
+    /// chunk only look that chunk up once, so this is faster than many
+    /// calls to [`ChunkMap::insert`] when nearby cells are set together.
+    pub fn set_many(&mut self, cells: impl IntoIterator<Item = (i32, i32, Option<T>)>) {
+        let mut current: Option<(ChunkCoordinate, &mut Chunk<Option<T>>)> = None;
+        for (x, y, val) in cells {
+            let coord = ChunkCoordinate::get_from_coordinates(x, y);
+            if !matches!(current, Some((c, _)) if c == coord) {
+                current = match val {
+                    Some(_) => Some((coord, self.map.entry(coord).or_insert_with(empty_chunk))),
+                    // Don't create a chunk just to remove nothing from it.
+                    None => self.map.get_mut(&coord).map(|chunk| (coord, chunk)),
+                };
+            }
+            if let (Some((_, chunk)), Some(x_offset), Some(y_offset)) =
+                (&mut current, coord.x_offset(x), coord.y_offset(y))
+            {
+                chunk[x_offset][y_offset] = val;
+            }
+        }
+    }
+
+    /// Removes every `T` from the map, freeing all of its chunks.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+}
+
+impl<T: Clone + PartialEq> ChunkMap<T> {
+    /// Replaces the value at `(x, y)`, and every cell connected to it
+    /// (up, down, left or right) with the same value, with `replacement`;
+    /// like a paint-bucket tool. If `(x, y)` is empty, the connected
+    /// empty cells are filled instead.
+    ///
+    /// Since the map is infinite, only cells at most `max_distance` cells
+    /// across or down from `(x, y)` are filled. Returns `true` if the fill
+    /// stopped there, leaving connected cells further away unfilled.
+    pub fn flood_fill(&mut self, x: i32, y: i32, replacement: T, max_distance: u32) -> bool {
+        let target = self.get(x, y).cloned();
+        if target.as_ref() == Some(&replacement) {
+            return false;
+        }
+        let in_bounds = |cx: i32, cy: i32| {
+            (i64::from(cx) - i64::from(x)).abs() <= i64::from(max_distance)
+                && (i64::from(cy) - i64::from(y)).abs() <= i64::from(max_distance)
+        };
+        let mut hit_limit = false;
+
+        let mut to_fill = VecDeque::from([(x, y)]);
+        while let Some((cx, cy)) = to_fill.pop_front() {
+            if self.get(cx, cy) != target.as_ref() {
+                continue;
+            }
+            self.insert(cx, cy, replacement.clone());
+            let neighbours = [
+                (cx.checked_sub(1), Some(cy)),
+                (cx.checked_add(1), Some(cy)),
+                (Some(cx), cy.checked_sub(1)),
+                (Some(cx), cy.checked_add(1)),
+            ];
+            for (nx, ny) in neighbours {
+                if let (Some(nx), Some(ny)) = (nx, ny) {
+                    if in_bounds(nx, ny) {
+                        to_fill.push_back((nx, ny));
+                    } else if self.get(nx, ny) == target.as_ref() {
+                        hit_limit = true;
+                    }
+                }
+            }
+        }
+        hit_limit
+    }
+}
+
+/// Two maps are equal if they have the same values in the same cells.
+/// Which chunks each map happens to have created doesn't matter, so a map
+/// which had a value inserted and then removed is equal to an empty one.
+impl<T: PartialEq> PartialEq for ChunkMap<T> {
+    fn eq(&self, other: &ChunkMap<T>) -> bool {
+        let is_empty = |chunk: &Chunk<Option<T>>| chunk.iter().flatten().all(Option::is_none);
+        let matches_in = |a: &ChunkMap<T>, b: &ChunkMap<T>| {
+            a.map.iter().all(|(coord, chunk)| match b.map.get(coord) {
+                Some(other_chunk) => chunk == other_chunk,
+                None => is_empty(chunk),
+            })
+        };
+        matches_in(self, other) && matches_in(other, self)
+    }
+}
+
+impl<T: Eq> Eq for ChunkMap<T> {}
+
+impl<T: Clone> Default for ChunkMap<T> {
+    fn default() -> ChunkMap<T> {
+        ChunkMap::new()
+    }
+}
+
+/// A view into a single cell of a [`ChunkMap`], which may or may not have
+/// a value in it. This is returned by [`ChunkMap::entry`], and works like
+/// [`std::collections::hash_map::Entry`].
+#[derive(Debug)]
+pub enum Entry<'a, T> {
+    /// The cell has a value in it.
+    Occupied(OccupiedEntry<'a, T>),
+    /// The cell is empty.
+    Vacant(VacantEntry<'a, T>),
+}
+
+/// A cell of a [`ChunkMap`] with a value in it. See [`Entry`].
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, T> {
+    /// Always `Some`.
+    slot: &'a mut Option<T>,
+}
+
+/// An empty cell of a [`ChunkMap`]. See [`Entry`].
+#[derive(Debug)]
+pub struct VacantEntry<'a, T> {
+    /// The map's chunks; the cell's chunk may not exist yet.
+    map: &'a mut HashMap<ChunkCoordinate, Chunk<Option<T>>>,
+    coord: ChunkCoordinate,
+    x_offset: usize,
+    y_offset: usize,
+}
+
+impl<'a, T> Entry<'a, T> {
+    /// Inserts `default` if the cell is empty, then returns a
+    /// mutable reference to the value in the cell.
+    pub fn or_insert(self, default: T) -> &'a mut T {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the cell is empty, then
+    /// returns a mutable reference to the value in the cell.
+    pub fn or_insert_with<F: FnOnce() -> T>(self, default: F) -> &'a mut T {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Calls `f` on the value in the cell, if there is one.
+    pub fn and_modify<F: FnOnce(&mut T)>(mut self, f: F) -> Entry<'a, T> {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, T: Default> Entry<'a, T> {
+    /// Inserts `T::default()` if the cell is empty, then returns a
+    /// mutable reference to the value in the cell.
+    pub fn or_default(self) -> &'a mut T {
+        self.or_insert_with(T::default)
+    }
+}
+
+impl<'a, T> OccupiedEntry<'a, T> {
+    /// Returns a reference to the value in the cell.
+    pub fn get(&self) -> &T {
+        self.slot.as_ref().expect("occupied entries have a value")
+    }
+
+    /// Returns a mutable reference to the value in the cell.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.slot.as_mut().expect("occupied entries have a value")
+    }
+
+    /// Returns a mutable reference to the value in the cell, which
+    /// lives as long as the map it came from.
+    pub fn into_mut(self) -> &'a mut T {
+        self.slot.as_mut().expect("occupied entries have a value")
+    }
+
+    /// Replaces the value in the cell, returning the old one.
+    pub fn insert(&mut self, value: T) -> T {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the value from the cell, returning it.
+    pub fn remove(self) -> T {
+        self.slot.take().expect("occupied entries have a value")
+    }
+}
+
+impl<'a, T> VacantEntry<'a, T> {
+    /// Inserts `value` into the cell, returning a mutable reference to it.
+    pub fn insert(self, value: T) -> &'a mut T {
+        let chunk = self.map.entry(self.coord).or_insert_with(empty_chunk);
+        chunk[self.x_offset][self.y_offset].insert(value)
+    }
+}
+
+impl<T: Clone> Extend<(i32, i32, T)> for ChunkMap<T> {
+    /// Inserts every `(x, y, val)`, as if by [`ChunkMap::insert`].
+    fn extend<I: IntoIterator<Item = (i32, i32, T)>>(&mut self, iter: I) {
+        self.set_many(iter.into_iter().map(|(x, y, val)| (x, y, Some(val))));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::ChunkCoordinate;
     use super::ChunkMap;
+    use super::Entry;
 
     #[test]
     fn check_chunk_coordinate() {
@@ -115,6 +522,48 @@ mod tests {
         let c = ChunkCoordinate::get_from_coordinates(72, 3);
         assert!(c.x == 64);
         assert!(c.y == 0);
+        let c = ChunkCoordinate::get_from_coordinates(i32::MIN, i32::MAX);
+        assert!(c.x == i32::MIN);
+        assert!(c.y == i32::MAX - 31);
+        assert_eq!(c.x_offset(i32::MIN + 5), Some(5));
+        assert_eq!(c.x_offset(i32::MAX), None);
+        assert_eq!(c.y_offset(i32::MIN), None);
+    }
+
+    #[test]
+    fn check_chunkmap_chunk_origin_of() {
+        assert_eq!(ChunkMap::<i32>::chunk_size(), 32);
+        assert_eq!(ChunkMap::<i32>::chunk_origin_of(31, 32), (0, 32));
+        assert_eq!(ChunkMap::<i32>::chunk_origin_of(-1, -32), (-32, -32));
+        assert_eq!(
+            ChunkMap::<i32>::chunk_origin_of(i32::MIN, i32::MAX),
+            (i32::MIN, i32::MAX - 31)
+        );
+    }
+
+    #[test]
+    fn extreme_coordinates_dont_overflow() {
+        let mut c = ChunkMap::<i32>::new();
+        let corners = [
+            (i32::MAX, i32::MAX),
+            (i32::MIN, i32::MIN),
+            (i32::MIN, i32::MAX),
+            (i32::MAX, i32::MIN),
+        ];
+        for (i, (x, y)) in corners.into_iter().enumerate() {
+            c.insert(x, y, i as i32);
+        }
+        for (i, (x, y)) in corners.into_iter().enumerate() {
+            assert_eq!(c.get(x, y), Some(&(i as i32)));
+        }
+        assert_eq!(c.iter().count(), 4);
+        assert_eq!(
+            c.region_to_vec(i32::MAX - 1, i32::MAX, 3, 1),
+            vec![vec![None, Some(0), None]]
+        );
+        c.flood_fill(i32::MAX, i32::MAX, 9, 4);
+        assert_eq!(c.get(i32::MAX, i32::MAX), Some(&9));
+        assert_eq!(c.remove(i32::MIN, i32::MIN), Some(1));
     }
 
     #[test]
@@ -171,4 +620,164 @@ mod tests {
         assert_eq!(c.get(5, 3), None);
         assert_eq!(c.get(65, 3), None);
     }
+
+    #[test]
+    fn check_chunkmap_clear() {
+        let mut c = ChunkMap::<i32>::new();
+        c.insert(4, 3, 1);
+        c.insert(-100, 250, 2);
+        c.clear();
+        assert_eq!(c.get(4, 3), None);
+        assert_eq!(c.get(-100, 250), None);
+    }
+
+    #[test]
+    fn check_chunkmap_get_or_insert_with() {
+        let mut c = ChunkMap::<i32>::new();
+        assert_eq!(*c.get_or_insert_with(-5, 40, || 3), 3);
+        *c.get_or_insert_with(-5, 40, || unreachable!()) += 1;
+        assert_eq!(c.get(-5, 40), Some(&4));
+    }
+
+    #[test]
+    fn check_chunkmap_entry() {
+        let mut c = ChunkMap::<i32>::new();
+        assert!(matches!(c.entry(2, 2), Entry::Vacant(_)));
+        *c.entry(2, 2).or_default() += 5;
+        c.entry(2, 2).and_modify(|v| *v *= 2).or_insert(0);
+        assert_eq!(c.get(2, 2), Some(&10));
+
+        match c.entry(2, 2) {
+            Entry::Occupied(entry) => assert_eq!(entry.remove(), 10),
+            Entry::Vacant(_) => panic!("(2, 2) should be occupied"),
+        }
+        assert_eq!(c.get(2, 2), None);
+    }
+
+    #[test]
+    fn check_chunkmap_vacant_entry_doesnt_create_chunk() {
+        let mut c = ChunkMap::<i32>::new();
+        c.entry(100, 100).and_modify(|v| *v += 1);
+        assert_eq!(c.chunks().count(), 0);
+        c.entry(100, 100).or_insert(1);
+        assert_eq!(c.chunks().count(), 1);
+    }
+
+    #[test]
+    fn check_chunkmap_region_to_vec() {
+        let mut c = ChunkMap::<i32>::new();
+        c.insert(-1, 0, 1);
+        c.insert(0, 1, 2);
+        assert_eq!(
+            c.region_to_vec(-1, 0, 2, 2),
+            vec![vec![Some(1), None], vec![None, Some(2)]]
+        );
+        assert_eq!(c.region_to_vec(i32::MAX, 0, 2, 1), vec![vec![None, None]]);
+        assert!(c.region_to_vec(0, 0, 5, 0).is_empty());
+    }
+
+    #[test]
+    fn check_chunkmap_flood_fill() {
+        let mut c = ChunkMap::<char>::new();
+        // A 3x3 box of walls with an empty middle.
+        for i in 0..3 {
+            for (x, y) in [(i, 0), (i, 2), (0, i), (2, i)] {
+                c.insert(x, y, '#');
+            }
+        }
+        assert!(!c.flood_fill(1, 1, '.', 128));
+        assert_eq!(c.get(1, 1), Some(&'.'));
+        assert_eq!(c.get(3, 1), None);
+
+        assert!(!c.flood_fill(0, 0, '+', 128));
+        assert_eq!(c.get(2, 2), Some(&'+'));
+        assert_eq!(c.get(1, 1), Some(&'.'));
+
+        assert!(c.flood_fill(10, 10, '~', 20));
+        assert_eq!(c.get(3, 1), Some(&'~'));
+        assert_eq!(c.get(1, 1), Some(&'.'));
+        let edge = 10 + 20;
+        assert_eq!(c.get(edge, edge), Some(&'~'));
+        assert_eq!(c.get(edge + 1, 10), None);
+    }
+
+    #[test]
+    fn check_chunkmap_iter() {
+        let mut c = ChunkMap::<i32>::new();
+        c.insert(-40, 3, 1);
+        c.insert(5, 70, 2);
+        c.insert(5, 71, 3);
+        c.remove(5, 71);
+        let mut cells: Vec<_> = c.iter().collect();
+        cells.sort();
+        assert_eq!(cells, vec![(-40, 3, &1), (5, 70, &2)]);
+    }
+
+    #[test]
+    fn check_chunkmap_chunks() {
+        let mut c = ChunkMap::new();
+        c.insert(1, 2, 'a');
+        c.insert(40, 2, 'b');
+        c.insert(41, 3, 'c');
+        let mut chunks: Vec<_> = c
+            .chunks()
+            .map(|(x, y, chunk)| (x, y, chunk.iter().flatten().flatten().count()))
+            .collect();
+        chunks.sort();
+        assert_eq!(chunks, vec![(0, 0, 1), (32, 0, 2)]);
+        let (_, _, chunk) = c.chunks().find(|&(x, _, _)| x == 32).unwrap();
+        assert_eq!(chunk[9][3], Some('c'));
+    }
+
+    #[test]
+    fn check_chunkmap_cells() {
+        let mut c = ChunkMap::<char>::new();
+        c.insert(5, 70, 'b');
+        c.insert(-40, 70, 'a');
+        c.insert(100, 3, 'c');
+        assert_eq!(c.cells(), vec![(100, 3, 'c'), (-40, 70, 'a'), (5, 70, 'b')]);
+    }
+
+    #[test]
+    fn check_chunkmap_count_in_region() {
+        let mut c = ChunkMap::<i32>::new();
+        c.extend((-50..50).map(|i| (i, i, i)));
+        assert_eq!(c.count_in_region(-50, -50, 100, 100), 100);
+        assert_eq!(c.count_in_region(-10, -5, 20, 40), 15);
+        assert_eq!(c.count_in_region(0, 0, 0, 10), 0);
+        c.insert(i32::MAX, i32::MAX, 1);
+        assert_eq!(c.count_in_region(i32::MAX, i32::MAX, u32::MAX, u32::MAX), 1);
+    }
+
+    #[test]
+    fn check_chunkmap_set_many() {
+        let mut c = ChunkMap::<i32>::new();
+        c.extend((0..100).map(|x| (x, -x, x)));
+        assert_eq!(c.get(0, 0), Some(&0));
+        assert_eq!(c.get(99, -99), Some(&99));
+
+        c.set_many([(0, 0, None), (1, -1, Some(10)), (500, 500, None)]);
+        assert_eq!(c.get(0, 0), None);
+        assert_eq!(c.get(1, -1), Some(&10));
+        assert_eq!(c.get(500, 500), None);
+        assert_eq!(c.map.len(), 8);
+    }
+
+    #[test]
+    fn check_chunkmap_eq_ignores_empty_chunks() {
+        let mut a = ChunkMap::<i32>::new();
+        let mut b = ChunkMap::<i32>::new();
+        a.insert(1, 2, 3);
+        b.insert(1, 2, 3);
+        b.insert(100, -100, 4);
+        assert_ne!(a, b);
+        assert_ne!(b, a);
+
+        b.remove(100, -100);
+        assert_eq!(a, b);
+        assert_eq!(b, a);
+
+        a.insert(1, 2, 4);
+        assert_ne!(a, b);
+    }
 }
@@ -0,0 +1,146 @@
+use super::{CharChunkMap, CharView, ViewportLocation};
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Modifier,
+    widgets::{Block, Widget},
+};
+
+/// A widget that shows a zoomed-out overview of a [`CharChunkMap`], with
+/// one part of the map (usually what the player can currently see)
+/// marked. The marked part is kept in the middle of the minimap.
+///
+/// Like [`CharView::scale`], each cell shows only every `scale`th
+/// coordinate of the map, so small details may not appear.
+///
+/// ```rust
+/// use termgame::{CharChunkMap, Minimap, ViewportLocation};
+/// let map = CharChunkMap::new();
+/// let minimap = Minimap::new(&map)
+///     .scale(4)
+///     .mark(ViewportLocation { x: 0, y: 0 }, (78, 22));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Minimap<'a> {
+    /// The map being shown.
+    data: &'a CharChunkMap,
+    /// The tui-rs [`Block`].
+    block: Option<Block<'a>>,
+    /// How many coordinates each cell of the widget moves along the map.
+    scale: u32,
+    /// The top-left and size of the marked part of the map.
+    mark: (ViewportLocation, (u16, u16)),
+}
+
+impl<'a> Minimap<'a> {
+    /// Creates a minimap of `data`, centred on `(0, 0)`, with a scale of `1`.
+    pub fn new(data: &'a CharChunkMap) -> Minimap<'a> {
+        Minimap {
+            data,
+            block: None,
+            scale: 1,
+            mark: (ViewportLocation::default(), (0, 0)),
+        }
+    }
+
+    /// Saves the tui-rs [`Block`] in this struct.
+    pub fn block(mut self, block: Block<'a>) -> Minimap<'a> {
+        self.block = Some(block);
+        self
+    }
+
+    /// Show every `scale`th coordinate of the map; see [`CharView::scale`].
+    pub fn scale(mut self, scale: u32) -> Minimap<'a> {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Mark the `(width, height)` coordinates with `top_left` at the
+    /// top-left, by drawing them reversed, and centre the minimap on them.
+    pub fn mark(mut self, top_left: ViewportLocation, size: (u16, u16)) -> Minimap<'a> {
+        self.mark = (top_left, size);
+        self
+    }
+}
+
+impl<'a> Widget for Minimap<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        // Whatever was drawn here before would be confusing on a minimap.
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf.get_mut(x, y).reset();
+            }
+        }
+        let area = match self.block.take() {
+            Some(b) => {
+                let inner_area = b.inner(area);
+                b.render(area, buf);
+                inner_area
+            }
+            None => area,
+        };
+
+        let scale = i64::from(self.scale);
+        let (top_left, (width, height)) = self.mark;
+        let (mark_x, mark_y) = (i64::from(top_left.x), i64::from(top_left.y));
+        let (mark_width, mark_height) = (i64::from(width), i64::from(height));
+        let clamp = |n: i64| n.clamp(i32::MIN.into(), i32::MAX.into()) as i32;
+        let viewport = ViewportLocation {
+            x: clamp(mark_x + mark_width / 2 - i64::from(area.width) * scale / 2),
+            y: clamp(mark_y + mark_height / 2 - i64::from(area.height) * scale / 2),
+        };
+        CharView::new(self.data)
+            .viewport(viewport)
+            .scale(self.scale)
+            .render(area, buf);
+
+        // A cell is marked if any of the coordinates it covers are.
+        let covers = |cell: u16, start: u16, origin: i32, mark: i64, len: i64| {
+            let from = i64::from(origin) + i64::from(cell - start) * scale;
+            from < mark + len && from + scale > mark
+        };
+        for y in area.top()..area.bottom() {
+            if !covers(y, area.top(), viewport.y, mark_y, mark_height) {
+                continue;
+            }
+            for x in area.left()..area.right() {
+                if covers(x, area.left(), viewport.x, mark_x, mark_width) {
+                    let cell = buf.get_mut(x, y);
+                    cell.modifier.insert(Modifier::REVERSED);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Minimap;
+    use crate::{CharChunkMap, StyledCharacter, ViewportLocation};
+    use tui::{buffer::Buffer, layout::Rect, style::Modifier, widgets::Widget};
+
+    #[test]
+    fn mark_is_centred_and_reversed() {
+        let mut map = CharChunkMap::new();
+        map.insert(0, 0, StyledCharacter::new('a').into());
+        map.insert(20, 8, StyledCharacter::new('b').into());
+
+        let area = Rect::new(0, 0, 10, 5);
+        let mut buf = Buffer::empty(area);
+        Minimap::new(&map)
+            .scale(4)
+            .mark(ViewportLocation { x: 0, y: 0 }, (8, 4))
+            .render(area, &mut buf);
+
+        // The minimap starts at (-16, -8), so (0, 0) is at (4, 2) and
+        // (20, 8) is at (9, 4).
+        assert_eq!(buf.get(4, 2).symbol, "a");
+        assert_eq!(buf.get(9, 4).symbol, "b");
+        for (x, y) in [(4, 2), (5, 2)] {
+            assert!(buf.get(x, y).modifier.contains(Modifier::REVERSED));
+        }
+        for (x, y) in [(3, 2), (6, 2), (4, 1), (4, 3)] {
+            assert!(!buf.get(x, y).modifier.contains(Modifier::REVERSED));
+        }
+    }
+}
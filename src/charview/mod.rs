@@ -1,15 +1,20 @@
 #![warn(missing_docs)]
 
+use std::collections::HashSet;
 use tui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
     widgets::{Block, Widget},
 };
+use unicode_width::UnicodeWidthChar;
 
 pub mod chunkmap;
+pub mod minimap;
 pub mod screen_character;
 
 use super::charview::{chunkmap::ChunkMap, screen_character::ScreenCharacter};
+use crate::{GameStyle, StyledCharacter};
 
 /// This is a [`ChunkMap`] (an infinite 2D map) of the internal representation
 /// of characters.
@@ -23,8 +28,102 @@ use super::charview::{chunkmap::ChunkMap, screen_character::ScreenCharacter};
 /// Use the [`crate::StyledCharacter`] for interacting with the `GameType` instead.
 pub type CharChunkMap = ChunkMap<ScreenCharacter>;
 
+impl CharChunkMap {
+    /// Write `text` into the map, starting at `(x, y)`, with every
+    /// character given `style`. A newline in `text` moves back to column
+    /// `x`, one row down. This is useful for building maps outside of a
+    /// running game, like in a level loader or a test.
+    ///
+    /// Returns the coordinate just past the last character written, so
+    /// that more text can be written after it.
+    ///
+    /// ```rust
+    /// use termgame::{CharChunkMap, GameColor, GameStyle};
+    /// let mut map = CharChunkMap::new();
+    /// let red = GameStyle::new().color(Some(GameColor::Red));
+    /// let end = map.write_str(2, 0, "#..\n#.@", Some(red));
+    /// assert_eq!(end, (5, 1));
+    /// assert_eq!(map.get(4, 1).map(|c| c.c), Some('@'));
+    /// ```
+    pub fn write_str(
+        &mut self,
+        x: i32,
+        y: i32,
+        text: &str,
+        style: Option<GameStyle>,
+    ) -> (i32, i32) {
+        let (mut col, mut row) = (x, y);
+        let mut cells = Vec::new();
+        for c in text.chars() {
+            if c == '\n' {
+                (col, row) = (x, row.saturating_add(1));
+                continue;
+            }
+            let character = StyledCharacter {
+                c,
+                style: style.clone(),
+            };
+            cells.push((col, row, character.into()));
+            col = col.saturating_add(1);
+        }
+        self.extend(cells);
+        (col, row)
+    }
+
+    /// Read the characters drawn in `area` of `buffer` back into a map,
+    /// keeping their styles. This undoes [`CharView`]: the cell at the
+    /// top-left of `area` becomes `(0, 0)` in the map, and blank cells
+    /// with no style are left empty. That makes it useful for taking a
+    /// snapshot of the screen, or checking what was drawn in a test.
+    ///
+    /// Only the first character of each cell is kept, and colours which
+    /// are reset to the terminal's default are treated as not being set.
+    ///
+    /// ```rust
+    /// use termgame::{CharChunkMap, Rect};
+    /// use tui::buffer::Buffer;
+    /// let buffer = Buffer::with_lines(vec!["#..", " @"]);
+    /// let map = CharChunkMap::from_buffer(&buffer, Rect::new(1, 0, 2, 2));
+    /// assert_eq!(map.get(0, 1).map(|c| c.c), Some('@'));
+    /// assert_eq!(map.get(-1, 1), None);
+    /// ```
+    pub fn from_buffer(buffer: &Buffer, area: Rect) -> CharChunkMap {
+        let Some(area) = crate::clip(area, buffer.area) else {
+            return CharChunkMap::new();
+        };
+        let mut cells = Vec::new();
+        for y in area.top()..area.bottom() {
+            let mut x = area.left();
+            while x < area.right() {
+                let cell = buffer.get(x, y);
+                let c = cell.symbol.chars().next().unwrap_or(' ');
+                let is_set = |color: Color| Some(color).filter(|c| *c != Color::Reset);
+                let style = Style {
+                    fg: is_set(cell.fg),
+                    bg: is_set(cell.bg),
+                    add_modifier: cell.modifier,
+                    sub_modifier: Modifier::empty(),
+                };
+                let style = Some(style).filter(|style| *style != Style::default());
+                if c != ' ' || style.is_some() {
+                    let (col, row) = (i32::from(x - area.left()), i32::from(y - area.top()));
+                    cells.push((col, row, ScreenCharacter { c, style }));
+                }
+                // The cell after a wide character is covered by it.
+                x += match c.width() {
+                    Some(2) => 2,
+                    _ => 1,
+                };
+            }
+        }
+        let mut map = CharChunkMap::new();
+        map.extend(cells);
+        map
+    }
+}
+
 /// The [`ViewportLocation`] describes the top-left
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct ViewportLocation {
     /// The x coordinate.
     pub x: i32,
@@ -32,6 +131,15 @@ pub struct ViewportLocation {
     pub y: i32,
 }
 
+/// How a [`CharView`] is divided when it shows two viewports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// The viewports are shown side by side; the first on the left.
+    Horizontal,
+    /// The viewports are shown one above the other; the first on top.
+    Vertical,
+}
+
 /// A widget that shows a small view into an infinitely sized map.
 #[derive(Debug, Clone)]
 pub struct CharView<'a> {
@@ -41,6 +149,16 @@ pub struct CharView<'a> {
     block: Option<Block<'a>>,
     /// The leftmost x value shown in the viewport.
     viewport: ViewportLocation,
+    /// If Some, what is shown in cells with nothing in them.
+    background: Option<ScreenCharacter>,
+    /// How many coordinates each cell of the widget moves along the map.
+    scale: u32,
+    /// If Some, a second viewport shown alongside the first.
+    split: Option<(ViewportLocation, SplitDirection)>,
+    /// If Some, coordinates which are drawn as if they were empty.
+    hidden: Option<&'a HashSet<(i32, i32)>>,
+    /// If true, each cell shows two rows of the map as coloured half blocks.
+    half_blocks: bool,
 }
 
 impl<'a> CharView<'a> {
@@ -49,6 +167,11 @@ impl<'a> CharView<'a> {
         CharView {
             block: None,
             viewport: ViewportLocation { x: 0, y: 0 },
+            background: None,
+            scale: 1,
+            split: None,
+            hidden: None,
+            half_blocks: false,
             data,
         }
     }
@@ -86,6 +209,172 @@ impl<'a> CharView<'a> {
         self.viewport = viewport;
         self
     }
+
+    /// Show the given character in every cell of the widget that has
+    /// nothing in it. By default, empty cells are left as they are.
+    ///
+    /// ```rust
+    /// use termgame::{CharChunkMap, CharView, GameColor, GameStyle, StyledCharacter};
+    /// let map = CharChunkMap::new();
+    /// let water = StyledCharacter::new('~').style(GameStyle::new().color(Some(GameColor::Blue)));
+    /// let charview = CharView::new(&map).background(water);
+    /// ```
+    pub fn background(mut self, background: StyledCharacter) -> CharView<'a> {
+        self.background = Some(background.into());
+        self
+    }
+
+    /// Zoom out, so that each cell of the widget shows every `scale`th
+    /// coordinate of the map. That is, the cell `(x, y)` cells from the
+    /// top-left shows the character at `(viewport.x + x * scale, viewport.y + y * scale)`.
+    /// Everything in between is skipped, which makes this useful for a
+    /// cheap overview or minimap.
+    ///
+    /// By default the scale is `1`, which shows every coordinate. A scale
+    /// of `0` is treated as `1`.
+    pub fn scale(mut self, scale: u32) -> CharView<'a> {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Divide the widget in two, showing a second view of the same map
+    /// (whose top-left is `viewport`) next to the first. This is useful
+    /// for split-screen games, where each player has their own camera.
+    ///
+    /// ```rust
+    /// use termgame::{CharChunkMap, CharView, SplitDirection, ViewportLocation};
+    /// let map = CharChunkMap::new();
+    /// let charview = CharView::new(&map)
+    ///     .viewport(ViewportLocation { x: 0, y: 0 })
+    ///     .split(ViewportLocation { x: 100, y: 0 }, SplitDirection::Horizontal);
+    /// ```
+    pub fn split(mut self, viewport: ViewportLocation, direction: SplitDirection) -> CharView<'a> {
+        self.split = Some((viewport, direction));
+        self
+    }
+
+    /// Draw the characters at the given coordinates as if there was nothing
+    /// there. This is useful for making characters blink.
+    pub fn hidden(mut self, hidden: &'a HashSet<(i32, i32)>) -> CharView<'a> {
+        self.hidden = Some(hidden);
+        self
+    }
+
+    /// Treat the map as a picture, where each character is one square
+    /// "pixel" of colour. Each cell of the widget shows two rows of the map,
+    /// using the `▀` character with the top pixel's colour in front and the
+    /// bottom pixel's colour behind. Since terminal cells are about twice as
+    /// tall as they are wide, this makes the pixels look square.
+    ///
+    /// A pixel's colour is its background colour, or if it doesn't have
+    /// one, its text colour. Characters with neither are drawn white, and
+    /// the character itself is never shown.
+    ///
+    /// ```rust
+    /// use termgame::{CharChunkMap, CharView, GameColor, GameStyle, StyledCharacter};
+    /// let mut map = CharChunkMap::new();
+    /// let red = GameStyle::new().background_color(Some(GameColor::Red));
+    /// map.insert(0, 0, StyledCharacter::new(' ').style(red).into());
+    /// let charview = CharView::new(&map).half_blocks(true);
+    /// ```
+    pub fn half_blocks(mut self, half_blocks: bool) -> CharView<'a> {
+        self.half_blocks = half_blocks;
+        self
+    }
+
+    /// The character at `(x, y)`, unless it is hidden.
+    fn visible_char(&self, x: i32, y: i32) -> Option<&ScreenCharacter> {
+        let is_hidden = self.hidden.is_some_and(|hidden| hidden.contains(&(x, y)));
+        match is_hidden {
+            true => None,
+            false => self.data.get(x, y),
+        }
+        .or(self.background.as_ref())
+    }
+
+    /// Draw the part of the map with `viewport` at the top-left into `area`.
+    fn render_viewport(&self, area: Rect, viewport: ViewportLocation, buf: &mut Buffer) {
+        let scale = i32::try_from(self.scale).unwrap_or(i32::MAX);
+        if self.half_blocks {
+            self.render_half_blocks(area, viewport, scale, buf);
+            return;
+        }
+        for y in area.top()..area.bottom() {
+            let mut x = area.left();
+            while x < area.right() {
+                let shifted_x: i32 = ((x - area.left()) as i32)
+                    .saturating_mul(scale)
+                    .saturating_add(viewport.x);
+                let shifted_y: i32 = ((y - area.top()) as i32)
+                    .saturating_mul(scale)
+                    .saturating_add(viewport.y);
+
+                if let Some(screen_character) = self.visible_char(shifted_x, shifted_y) {
+                    let style = screen_character.style.unwrap_or_default();
+                    // Wide characters (like CJK or emoji) take up two cells,
+                    // so the cell they cover is cleared and skipped. If there
+                    // is no room for one, a space is drawn instead.
+                    if screen_character.c.width() == Some(2) {
+                        if x + 1 < area.right() {
+                            buf.get_mut(x, y)
+                                .set_char(screen_character.c)
+                                .set_style(style);
+                            buf.get_mut(x + 1, y).reset();
+                            buf.get_mut(x + 1, y).set_style(style);
+                            x += 2;
+                            continue;
+                        }
+                        buf.get_mut(x, y).set_char(' ').set_style(style);
+                    } else {
+                        buf.get_mut(x, y)
+                            .set_char(screen_character.c)
+                            .set_style(style);
+                    }
+                }
+                x += 1;
+            }
+        }
+    }
+
+    /// Draw the part of the map with `viewport` at the top-left into
+    /// `area`, two rows of the map to each row of cells; see
+    /// [`CharView::half_blocks`].
+    fn render_half_blocks(
+        &self,
+        area: Rect,
+        viewport: ViewportLocation,
+        scale: i32,
+        buf: &mut Buffer,
+    ) {
+        let pixel = |x: i32, y: i32| {
+            let style = self.visible_char(x, y)?.style.unwrap_or_default();
+            Some(style.bg.or(style.fg).unwrap_or(Color::White))
+        };
+        for y in area.top()..area.bottom() {
+            let top_y = ((y - area.top()) as i32)
+                .saturating_mul(scale.saturating_mul(2))
+                .saturating_add(viewport.y);
+            let bottom_y = top_y.saturating_add(scale);
+            for x in area.left()..area.right() {
+                let shifted_x: i32 = ((x - area.left()) as i32)
+                    .saturating_mul(scale)
+                    .saturating_add(viewport.x);
+                let cell = buf.get_mut(x, y);
+                match (pixel(shifted_x, top_y), pixel(shifted_x, bottom_y)) {
+                    (Some(top), Some(bottom)) => {
+                        cell.set_char('▀').set_fg(top).set_bg(bottom);
+                    }
+                    (Some(top), None) => {
+                        cell.set_char('▀').set_fg(top);
+                    }
+                    (None, Some(bottom)) => {
+                        cell.set_char('▄').set_fg(bottom);
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+    }
 }
 
 impl<'a> Widget for CharView<'a> {
@@ -102,17 +391,111 @@ impl<'a> Widget for CharView<'a> {
         if charview_area.height < 1 {
             return;
         }
-        for y in charview_area.top()..charview_area.bottom() {
-            for x in charview_area.left()..charview_area.right() {
-                let shifted_x: i32 = (x - charview_area.left()) as i32 + self.viewport.x;
-                let shifted_y: i32 = (y - charview_area.top()) as i32 + self.viewport.y;
-
-                if let Some(screen_character) = self.data.get(shifted_x, shifted_y) {
-                    buf.get_mut(x, y)
-                        .set_char(screen_character.c)
-                        .set_style((*screen_character).style.unwrap_or_default());
-                }
+        match self.split {
+            Some((second_viewport, direction)) => {
+                let direction = match direction {
+                    SplitDirection::Horizontal => Direction::Horizontal,
+                    SplitDirection::Vertical => Direction::Vertical,
+                };
+                let halves = Layout::default()
+                    .direction(direction)
+                    .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
+                    .split(charview_area);
+                self.render_viewport(halves[0], self.viewport, buf);
+                self.render_viewport(halves[1], second_viewport, buf);
             }
+            None => self.render_viewport(charview_area, self.viewport, buf),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CharChunkMap, CharView};
+    use crate::{GameColor, GameStyle, StyledCharacter};
+    use tui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+
+    #[test]
+    fn from_buffer_undoes_render() {
+        let mut map = CharChunkMap::new();
+        let red = GameStyle::new().color(Some(GameColor::Red));
+        map.write_str(0, 0, "a 世", Some(red));
+        // The wide character covers (3, 0).
+        map.write_str(4, 0, "b", None);
+        map.write_str(1, 1, "#", None);
+        map.insert(
+            0,
+            2,
+            StyledCharacter::new(' ')
+                .style(GameStyle::new().background_color(Some(GameColor::Blue)))
+                .into(),
+        );
+
+        let area = Rect::new(2, 1, 6, 3);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 5));
+        CharView::new(&map).render(area, &mut buf);
+        assert_eq!(CharChunkMap::from_buffer(&buf, area), map);
+
+        // Nothing is read from outside the buffer.
+        let outside = CharChunkMap::from_buffer(&buf, Rect::new(20, 0, 5, 5));
+        assert_eq!(outside, CharChunkMap::new());
+    }
+
+    #[test]
+    fn wide_characters_cover_two_cells() {
+        let mut map = CharChunkMap::new();
+        map.insert(0, 0, StyledCharacter::new('世').into());
+        map.insert(1, 0, StyledCharacter::new('a').into());
+        map.insert(2, 0, StyledCharacter::new('b').into());
+        map.insert(3, 0, StyledCharacter::new('界').into());
+
+        let area = Rect::new(0, 0, 4, 1);
+        let mut buf = Buffer::empty(area);
+        CharView::new(&map).render(area, &mut buf);
+
+        assert_eq!(buf.get(0, 0).symbol, "世");
+        assert_eq!(buf.get(1, 0).symbol, " ");
+        assert_eq!(buf.get(2, 0).symbol, "b");
+        // There's no room for the second half of this one.
+        assert_eq!(buf.get(3, 0).symbol, " ");
+    }
+
+    #[test]
+    fn half_blocks_show_two_rows_per_cell() {
+        let red = GameStyle::new().background_color(Some(GameColor::Red));
+        let blue = GameStyle::new().color(Some(GameColor::Blue));
+        let mut map = CharChunkMap::new();
+        map.insert(0, 0, StyledCharacter::new(' ').style(red.clone()).into());
+        map.insert(0, 1, StyledCharacter::new('x').style(blue).into());
+        map.insert(1, 3, StyledCharacter::new('y').style(red).into());
+
+        let area = Rect::new(0, 0, 3, 2);
+        let mut buf = Buffer::empty(area);
+        CharView::new(&map).half_blocks(true).render(area, &mut buf);
+
+        let cell = buf.get(0, 0);
+        assert_eq!(
+            (cell.symbol.as_str(), cell.fg, cell.bg),
+            ("▀", Color::Red, Color::Blue)
+        );
+        let cell = buf.get(1, 1);
+        assert_eq!((cell.symbol.as_str(), cell.fg), ("▄", Color::Red));
+        assert_eq!(buf.get(1, 0).symbol, " ");
+        assert_eq!(buf.get(2, 1).symbol, " ");
+    }
+
+    #[test]
+    fn write_str_wraps_at_newlines() {
+        let mut map = CharChunkMap::new();
+        assert_eq!(map.write_str(-1, 5, "ab\ncd\n", None), (-1, 7));
+        let cells: Vec<_> = map
+            .cells()
+            .into_iter()
+            .map(|(x, y, c)| (x, y, c.c))
+            .collect();
+        assert_eq!(
+            cells,
+            vec![(-1, 5, 'a'), (0, 5, 'b'), (-1, 6, 'c'), (0, 6, 'd')]
+        );
+    }
+}
@@ -3,7 +3,7 @@
 use std::default::Default;
 use tui::style::Style;
 
-#[derive(Debug, Clone, Default, Copy)]
+#[derive(Debug, Clone, Default, Copy, PartialEq)]
 /// A `ScreenCharacter` is a character that will be displayed
 /// on the screen.
 ///
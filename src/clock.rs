@@ -0,0 +1,71 @@
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Something which tells the game loop what time it is.
+///
+/// By default, the game follows the system's clock; see
+/// [`GameSettings::clock`](crate::GameSettings::clock) to use a
+/// different one (like a [`ManualClock`]).
+pub trait Clock {
+    /// The current time.
+    fn now(&self) -> Instant;
+
+    /// Called when the game waited `duration` for an event, and none came.
+    /// Clocks which follow real time don't need to do anything, since that
+    /// time has already passed.
+    fn waited(&self, _duration: Duration) {}
+}
+
+/// A [`Clock`] that follows the system's monotonic clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A [`Clock`] that only moves when it is told to, or when the game waits
+/// for an event and none comes. Clones of a `ManualClock` share the same
+/// time, so you can keep one and give a clone to the game.
+///
+/// Since time only passes when the game is idle, a game using a
+/// `ManualClock` runs the same number of ticks for the same events, no
+/// matter how fast the computer is.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Creates a clock, starting at the current time.
+    pub fn new() -> ManualClock {
+        ManualClock {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> ManualClock {
+        ManualClock::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn waited(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
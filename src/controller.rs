@@ -1,6 +1,8 @@
 #![warn(missing_docs)]
 
 use super::game::{Game, GameEvent};
+#[cfg(feature = "gamepad")]
+use crate::GamepadEvent;
 
 /// The [`Controller`] trait must be implemented on a struct
 /// in order to control a Termgame Game.
@@ -8,19 +10,78 @@ use super::game::{Game, GameEvent};
 /// Each of the event-handlers (i.e. functions) will be called as they
 /// are described, and passed a [`Game`] trait object; which gives you
 /// functions to control the game.
+///
+/// Every event-handler has a default implementation that does nothing,
+/// so you only need to implement the ones your game uses.
 pub trait Controller {
     /// This event-handler is called just before any other event-handlers.
     /// You should use it for any initialisation that needs to happen once
     /// per controller (though you could also initialise things before
     /// this is called if you prefer).
-    fn on_start(&mut self, game: &mut Game);
+    fn on_start(&mut self, _game: &mut Game) {}
 
     /// Whenever the user interacts with the Game, this event-handler will
     /// be called and the relevant [`GameEvent`] will be provided. Use this
     /// function to handle key-presses from the user.
-    fn on_event(&mut self, game: &mut Game, event: GameEvent);
+    fn on_event(&mut self, _game: &mut Game, _event: GameEvent) {}
+
+    /// Whenever something happens on a gamepad (like a button being
+    /// pressed), this event-handler is called with the [`GamepadEvent`].
+    /// Like key presses, pressing a button closes a message shown with
+    /// [`Game::show_modal`], and gamepads are ignored while
+    /// [`Game::prompt`] is waiting for text.
+    ///
+    /// This is only available with the `gamepad` feature.
+    ///
+    /// ```rust,no_run
+    /// use termgame::{run_game, Controller, Game, GameSettings, GamepadButton, GamepadEvent};
+    ///
+    /// struct MyGame {
+    ///     x: i32,
+    /// }
+    ///
+    /// impl Controller for MyGame {
+    ///     fn on_gamepad_event(&mut self, game: &mut Game, event: GamepadEvent) {
+    ///         match event {
+    ///             GamepadEvent::Pressed(_, GamepadButton::DPadLeft) => self.x -= 1,
+    ///             GamepadEvent::Pressed(_, GamepadButton::DPadRight) => self.x += 1,
+    ///             _ => {}
+    ///         }
+    ///         game.center_viewport_on(self.x, 0);
+    ///     }
+    /// }
+    ///
+    /// run_game(&mut MyGame { x: 0 }, GameSettings::new()).unwrap();
+    /// ```
+    #[cfg(feature = "gamepad")]
+    fn on_gamepad_event(&mut self, _game: &mut Game, _event: GamepadEvent) {}
+
+    /// This event-handler is called whenever the terminal is resized,
+    /// with the new `width` and `height` of the terminal. It is called
+    /// just before the resize event is passed to [`Controller::on_event`].
+    fn on_resize(&mut self, _game: &mut Game, _width: u16, _height: u16) {}
 
     /// This function is called between every time the Termgame is drawn.
     /// It allows you to make actions happen independently of user-input.
-    fn on_tick(&mut self, game: &mut Game);
+    fn on_tick(&mut self, _game: &mut Game) {}
+
+    /// This event-handler is called just before every frame is drawn,
+    /// whether or not a tick has happened since the last one. Use it for
+    /// things which only change how the game looks (like a pulsing
+    /// highlight), and [`Controller::on_tick`] for how the game plays.
+    fn on_render(&mut self, _game: &mut Game) {}
+
+    /// This event-handler is called when the player dismisses a message
+    /// shown with [`Game::show_modal`], by pressing any key.
+    fn on_modal_closed(&mut self, _game: &mut Game) {}
+
+    /// This event-handler is called when the player presses enter to finish
+    /// typing the text asked for with [`Game::prompt`], and is given `text`.
+    fn on_input_complete(&mut self, _game: &mut Game, _text: String) {}
+
+    /// This event-handler is called once, just before the game ends; either
+    /// because [`Game::end_game`] was called, or the quit event was received.
+    /// You can use it to save the game, or read the final state of the [`Game`].
+    /// No other event-handler will be called after this one.
+    fn on_end(&mut self, _game: &mut Game) {}
 }
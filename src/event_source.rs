@@ -0,0 +1,113 @@
+use crossterm::event::{self, Event};
+use std::{
+    collections::VecDeque,
+    io,
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+/// Somewhere the game loop gets its events from.
+///
+/// By default, events come from the terminal; see
+/// [`GameSettings::event_source`](crate::GameSettings::event_source)
+/// to give the game events from somewhere else as well.
+pub trait EventSource {
+    /// Wait at most `timeout` for an event. Returns `None`
+    /// if no event happened in that time.
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>>;
+}
+
+/// An [`EventSource`] which reads events from the terminal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TerminalEvents;
+
+impl EventSource for TerminalEvents {
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        if event::poll(timeout)? {
+            event::read().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Events sent down a channel are given to the game, so another thread
+/// can control it. Once every sender is gone, no more events happen.
+impl EventSource for Receiver<Event> {
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        match self.recv_timeout(timeout) {
+            Ok(event) => Ok(Some(event)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(timeout);
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// An [`EventSource`] which gives out events from another source as soon
+/// as they are ready, and otherwise waits for events from the terminal.
+pub(crate) struct AlongsideTerminal<'a>(pub(crate) &'a mut dyn EventSource);
+
+impl EventSource for AlongsideTerminal<'_> {
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        match self.0.next_event(Duration::ZERO)? {
+            Some(event) => Ok(Some(event)),
+            None => TerminalEvents.next_event(timeout),
+        }
+    }
+}
+
+/// An [`EventSource`] which gives out a list of events, one after
+/// another, and then never gives out anything else. It never actually
+/// waits, so it should be used with a [`ManualClock`](crate::ManualClock).
+#[derive(Debug)]
+pub(crate) struct ScriptedEvents {
+    events: VecDeque<Event>,
+}
+
+impl ScriptedEvents {
+    /// Creates a source giving out `events`.
+    pub(crate) fn new(events: Vec<Event>) -> ScriptedEvents {
+        ScriptedEvents {
+            events: events.into(),
+        }
+    }
+}
+
+impl EventSource for ScriptedEvents {
+    fn next_event(&mut self, _timeout: Duration) -> io::Result<Option<Event>> {
+        Ok(self.events.pop_front())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Event, EventSource, ScriptedEvents};
+    use crate::{KeyCode, SimpleEvent};
+    use std::{sync::mpsc, time::Duration};
+
+    #[test]
+    fn channel_gives_sent_events() {
+        let (sender, mut receiver) = mpsc::channel();
+        let event: Event = SimpleEvent::Just(KeyCode::Enter).into();
+        sender.send(event.clone()).unwrap();
+        assert_eq!(receiver.next_event(Duration::ZERO).unwrap(), Some(event));
+        assert_eq!(receiver.next_event(Duration::ZERO).unwrap(), None);
+        drop(sender);
+        assert_eq!(receiver.next_event(Duration::ZERO).unwrap(), None);
+    }
+
+    #[test]
+    fn scripted_events_run_out() {
+        let event: Event = SimpleEvent::Just(KeyCode::Enter).into();
+        let mut events = ScriptedEvents::new(vec![event.clone()]);
+        assert_eq!(
+            events.next_event(Duration::from_secs(1)).unwrap(),
+            Some(event)
+        );
+        assert_eq!(events.next_event(Duration::from_secs(1)).unwrap(), None);
+    }
+}
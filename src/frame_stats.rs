@@ -0,0 +1,74 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// How many frames and ticks the averages are taken over.
+const WINDOW: usize = 30;
+
+/// Keeps track of how quickly the game is drawing frames and running
+/// ticks, averaged over the last few of each.
+#[derive(Debug, Default)]
+pub(crate) struct FrameStats {
+    frames: VecDeque<Instant>,
+    tick_times: VecDeque<Duration>,
+}
+
+impl FrameStats {
+    /// Note that a frame was drawn at `now`.
+    pub(crate) fn record_frame(&mut self, now: Instant) {
+        if self.frames.len() == WINDOW {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(now);
+    }
+
+    /// Note that a tick took `duration` to run.
+    pub(crate) fn record_tick(&mut self, duration: Duration) {
+        if self.tick_times.len() == WINDOW {
+            self.tick_times.pop_front();
+        }
+        self.tick_times.push_back(duration);
+    }
+
+    /// The average number of frames drawn per second.
+    pub(crate) fn fps(&self) -> f64 {
+        match (self.frames.front(), self.frames.back()) {
+            (Some(first), Some(last)) if last > first => {
+                (self.frames.len() - 1) as f64 / last.duration_since(*first).as_secs_f64()
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// The average time [`Controller::on_tick`](crate::Controller::on_tick)
+    /// took to run.
+    pub(crate) fn average_tick_time(&self) -> Duration {
+        match self.tick_times.len() {
+            0 => Duration::ZERO,
+            n => self.tick_times.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrameStats;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn averages_recent_frames_and_ticks() {
+        let mut stats = FrameStats::default();
+        assert_eq!(stats.fps(), 0.0);
+        assert_eq!(stats.average_tick_time(), Duration::ZERO);
+
+        let start = Instant::now();
+        for i in 0..100 {
+            stats.record_frame(start + Duration::from_millis(i * 20));
+            stats.record_tick(Duration::from_millis(i));
+        }
+        assert!((stats.fps() - 50.0).abs() < 0.001);
+        // Only the last 30 ticks (70ms to 99ms) count.
+        assert_eq!(stats.average_tick_time(), Duration::from_micros(84_500));
+    }
+}
@@ -1,18 +1,46 @@
 #![warn(missing_docs)]
-use super::charview::screen_character::ScreenCharacter;
+use super::charview::{
+    chunkmap::Entry as ChunkMapEntry, screen_character::ScreenCharacter, SplitDirection,
+};
+use super::tween::lerp;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque},
+    hash::{Hash, Hasher},
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style as TuiStyle,
+    text::Span,
+    widgets::{Block, Borders, Widget},
+};
 
 pub use super::charview::{CharChunkMap, ViewportLocation};
 pub use tui::style::{Color, Modifier as Font};
 
 pub use crossterm::event::{
-    Event as GameEvent, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
+    Event as GameEvent, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton,
+    MouseEvent, MouseEventKind,
 };
 
-pub use super::{Message, SCREEN_HEIGHT, SCREEN_WIDTH};
+pub use super::{Anchor, Message, SCREEN_HEIGHT, SCREEN_WIDTH};
 
 pub use crate::styled_characters::{Style as GameStyle, StyledCharacter};
 
 /// This is an enum to make it easy to match on events.
+///
+/// Shift is only reported for keys that aren't characters; so typing
+/// `A` gives `SimpleEvent::Just(KeyCode::Char('A'))`, rather than
+/// `SimpleEvent::WithShift(KeyCode::Char('A'))`. Note that most terminals
+/// report Shift+Tab as `SimpleEvent::WithShift(KeyCode::BackTab)`.
+///
+/// Key releases (see [`GameSettings::keyboard_enhancement`](crate::GameSettings::keyboard_enhancement))
+/// are a [`SimpleEvent::ComplexEvent`], so that matching on a key only
+/// happens once each time it is pressed. Repeats of a held key are treated
+/// like presses, since that is how terminals which don't report repeats
+/// send them anyway.
 #[derive(Debug, PartialOrd, Clone, PartialEq, Eq, Hash)]
 pub enum SimpleEvent {
     /// This happens when the user holds Control
@@ -23,8 +51,44 @@ pub enum SimpleEvent {
     WithControlAlt(KeyCode),
     /// This happens when the user just presses a key
     Just(KeyCode),
+    /// This happens when the user presses the left mouse button. `x` and `y`
+    /// are the column and row of the terminal that was clicked, regardless
+    /// of which keys were held.
+    LeftClick {
+        /// The column that was clicked.
+        x: u16,
+        /// The row that was clicked.
+        y: u16,
+    },
+    /// This happens when the user presses the right mouse button. `x` and `y`
+    /// are the column and row of the terminal that was clicked, regardless
+    /// of which keys were held.
+    RightClick {
+        /// The column that was clicked.
+        x: u16,
+        /// The row that was clicked.
+        y: u16,
+    },
+    /// This happens when the user scrolls the mouse wheel while over
+    /// the column `x` and row `y` of the terminal.
+    Scroll {
+        /// The column the mouse was over.
+        x: u16,
+        /// The row the mouse was over.
+        y: u16,
+        /// `-1` if the user scrolled up, or `1` if they scrolled down.
+        lines: i16,
+    },
     /// This is when an event is more complicated than a keypress.
     ComplexEvent(GameEvent),
+    /// This happens when the user holds Shift
+    WithShift(KeyCode),
+    /// This happens when the user holds Control AND Shift
+    WithControlShift(KeyCode),
+    /// This happens when the user holds Alt AND Shift
+    WithAltShift(KeyCode),
+    /// This happens when the user holds Control, Alt AND Shift
+    WithControlAltShift(KeyCode),
 }
 
 impl From<SimpleEvent> for GameEvent {
@@ -33,51 +97,269 @@ impl From<SimpleEvent> for GameEvent {
             SimpleEvent::WithControl(c) => (c, KeyModifiers::CONTROL),
             SimpleEvent::WithAlt(c) => (c, KeyModifiers::ALT),
             SimpleEvent::WithControlAlt(c) => (c, KeyModifiers::CONTROL | KeyModifiers::ALT),
+            SimpleEvent::WithShift(c) => (c, KeyModifiers::SHIFT),
+            SimpleEvent::WithControlShift(c) => (c, KeyModifiers::CONTROL | KeyModifiers::SHIFT),
+            SimpleEvent::WithAltShift(c) => (c, KeyModifiers::ALT | KeyModifiers::SHIFT),
+            SimpleEvent::WithControlAltShift(c) => (
+                c,
+                KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+            ),
             SimpleEvent::Just(c) => (c, KeyModifiers::NONE),
+            SimpleEvent::LeftClick { x, y } => {
+                return mouse_event(MouseEventKind::Down(MouseButton::Left), x, y)
+            }
+            SimpleEvent::RightClick { x, y } => {
+                return mouse_event(MouseEventKind::Down(MouseButton::Right), x, y)
+            }
+            SimpleEvent::Scroll { x, y, lines } => {
+                let kind = match lines {
+                    ..=-1 => MouseEventKind::ScrollUp,
+                    _ => MouseEventKind::ScrollDown,
+                };
+                return mouse_event(kind, x, y);
+            }
             SimpleEvent::ComplexEvent(e) => return e,
         };
         GameEvent::Key(KeyEvent::new(c, modifiers))
     }
 }
 
+/// Create a mouse [`GameEvent`] with no modifiers held.
+fn mouse_event(kind: MouseEventKind, column: u16, row: u16) -> GameEvent {
+    GameEvent::Mouse(MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
 impl From<GameEvent> for SimpleEvent {
     fn from(event: GameEvent) -> SimpleEvent {
         const CONTROL_ALT: KeyModifiers = KeyModifiers::CONTROL.union(KeyModifiers::ALT);
+        const CONTROL_SHIFT: KeyModifiers = KeyModifiers::CONTROL.union(KeyModifiers::SHIFT);
+        const ALT_SHIFT: KeyModifiers = KeyModifiers::ALT.union(KeyModifiers::SHIFT);
+        const CONTROL_ALT_SHIFT: KeyModifiers = CONTROL_ALT.union(KeyModifiers::SHIFT);
         match event {
+            GameEvent::Key(KeyEvent {
+                kind: KeyEventKind::Release,
+                ..
+            }) => SimpleEvent::ComplexEvent(event),
             GameEvent::Key(KeyEvent {
                 code, modifiers, ..
-            }) => match modifiers.intersection(KeyModifiers::CONTROL | KeyModifiers::ALT) {
-                CONTROL_ALT => SimpleEvent::WithControlAlt(code),
-                KeyModifiers::CONTROL => SimpleEvent::WithControl(code),
-                KeyModifiers::ALT => SimpleEvent::WithAlt(code),
-                KeyModifiers::NONE => SimpleEvent::Just(code),
-                _ => unreachable!(),
+            }) => {
+                // Shift is already part of a character (e.g. 'A' vs 'a').
+                let mask = match code {
+                    KeyCode::Char(_) => CONTROL_ALT,
+                    _ => CONTROL_ALT_SHIFT,
+                };
+                match modifiers.intersection(mask) {
+                    CONTROL_ALT_SHIFT => SimpleEvent::WithControlAltShift(code),
+                    CONTROL_ALT => SimpleEvent::WithControlAlt(code),
+                    CONTROL_SHIFT => SimpleEvent::WithControlShift(code),
+                    ALT_SHIFT => SimpleEvent::WithAltShift(code),
+                    KeyModifiers::CONTROL => SimpleEvent::WithControl(code),
+                    KeyModifiers::ALT => SimpleEvent::WithAlt(code),
+                    KeyModifiers::SHIFT => SimpleEvent::WithShift(code),
+                    KeyModifiers::NONE => SimpleEvent::Just(code),
+                    // Should a new combination of modifiers ever show up,
+                    // don't crash the game over it.
+                    _ => SimpleEvent::ComplexEvent(event),
+                }
+            }
+            GameEvent::Mouse(MouseEvent {
+                kind, column, row, ..
+            }) => match kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    SimpleEvent::LeftClick { x: column, y: row }
+                }
+                MouseEventKind::Down(MouseButton::Right) => {
+                    SimpleEvent::RightClick { x: column, y: row }
+                }
+                MouseEventKind::ScrollUp => SimpleEvent::Scroll {
+                    x: column,
+                    y: row,
+                    lines: -1,
+                },
+                MouseEventKind::ScrollDown => SimpleEvent::Scroll {
+                    x: column,
+                    y: row,
+                    lines: 1,
+                },
+                _ => SimpleEvent::ComplexEvent(event),
             },
-            e => return SimpleEvent::ComplexEvent(e),
+            e => SimpleEvent::ComplexEvent(e),
         }
     }
 }
 
+/// Draws an overlay's widget into the given area.
+type RenderOverlay = Box<dyn Fn(Rect, &mut Buffer)>;
+
+/// A tui widget drawn on top of the game; see [`Game::set_overlay`].
+pub(super) struct Overlay {
+    name: String,
+    /// Where the widget is drawn, relative to the top-left of the game area.
+    pub(super) area: Rect,
+    render: RenderOverlay,
+}
+
+impl Widget for &Overlay {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (self.render)(area, buf)
+    }
+}
+
+/// Splits `text` into lines of at most `width` characters, breaking
+/// between words where possible.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    if width == 0 {
+        return lines;
+    }
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut line_len = 0;
+        for word in paragraph.split_whitespace() {
+            let mut word: Vec<char> = word.chars().collect();
+            if line_len > 0 && line_len + 1 + word.len() <= width {
+                line.push(' ');
+                line.extend(&word);
+                line_len += 1 + word.len();
+                continue;
+            }
+            if line_len > 0 {
+                lines.push(std::mem::take(&mut line));
+            }
+            while word.len() > width {
+                lines.push(word.drain(..width).collect());
+            }
+            line_len = word.len();
+            line.extend(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
 /// The Game struct is passed to all of the Controller's event methods,
 /// to allow the implementor to view and modify the state of the game.
 pub struct Game<'a> {
     /// This determines whether the game will end soon.
     pub(super) should_end: bool,
-    /// If Some, a message will be shown at the bottom of the screen.
+    /// While this is true, [`crate::Controller::on_tick`] is not called.
+    pub(super) paused: bool,
+    /// If true, the current message is a modal; see [`Game::show_modal`].
+    pub(super) modal: bool,
+    /// If Some, the current message is asking for text: the question, and
+    /// what has been typed so far; see [`Game::prompt`].
+    pub(super) input: Option<(String, String)>,
+    /// If true, the map is drawn as pixels; see [`Game::set_half_blocks`].
+    pub(super) half_blocks: bool,
+    /// If Some, a second viewport shown alongside the first; see
+    /// [`Game::set_split`].
+    pub(super) split: Option<(ViewportLocation, SplitDirection)>,
+    /// If true, the game is drawn again before waiting for the next event.
+    pub(super) redraw_requested: bool,
+    /// Coordinates which are flashing, with the style they flash and how
+    /// many more ticks they flash for.
+    pub(super) flashing: HashMap<(i32, i32), (GameStyle, u32)>,
+    /// Coordinates which blink, with how often they blink and when they started.
+    pub(super) blinking: HashMap<(i32, i32), (Duration, Instant)>,
+    /// If Some, the coordinates the terminal's cursor is shown at.
+    pub(super) cursor: Option<(i32, i32)>,
+    /// Rows of the screen which have changed since the game was last drawn.
+    pub(super) dirty_rows: HashSet<i32>,
+    /// If true, everything has changed since the game was last drawn.
+    pub(super) all_dirty: bool,
+    /// If Some, how long a key counts as held after it was last pressed.
+    pub(super) held_key_timeout: Option<Duration>,
+    /// The keys which might be held, and when they were last pressed.
+    pub(super) held_keys: HashMap<KeyCode, Instant>,
+    /// Whether the terminal has ever said a key was released.
+    pub(super) key_releases_seen: bool,
+    /// Widgets drawn on top of the game, in the order they were added.
+    pub(super) overlays: Vec<Overlay>,
+    /// Text fixed to the edges of the game area; see [`Game::draw_hud_text`].
+    pub(super) hud: Vec<(Anchor, (i16, i16), String)>,
+    /// Where the minimap is drawn, and its scale; see [`Game::draw_minimap`].
+    pub(super) minimap: Option<(Rect, u32)>,
+    /// If Some, a message will be shown above or below the game.
     pub(super) message: Option<Message>,
+    /// Messages waiting to be shown once the current message is removed.
+    pub(super) message_queue: VecDeque<Message>,
+    /// If Some, a line of text always shown at the bottom of the screen.
+    pub(super) status: Option<String>,
+    /// How many lines of the current message have been scrolled past.
+    pub(super) message_scroll: u16,
+    /// When the current message started being shown.
+    pub(super) message_shown_at: Instant,
     /// The place in the viewport that is currently the top-left pixel.
     pub(super) viewport: ViewportLocation,
+    /// If Some, the (top-left, bottom-right) corners the viewport is kept within.
+    pub(super) viewport_bounds: Option<(ViewportLocation, ViewportLocation)>,
+    /// If Some, the viewport is gliding (from, to), starting at the given
+    /// time and arriving after the given duration.
+    pub(super) viewport_scroll: Option<(ViewportLocation, ViewportLocation, Instant, Duration)>,
+    /// If Some, the screen is shaking: how far it shakes, when it started,
+    /// and how long it lasts.
+    pub(super) shake: Option<(u16, Instant, Duration)>,
+    /// Which borders are drawn around the game area.
+    pub(super) game_border: Borders,
+    /// If Some, the title shown on the game area's border.
+    pub(super) game_title: Option<String>,
+    /// If Some, the style of the game area's border and title.
+    pub(super) border_style: Option<GameStyle>,
+    /// The (width, height) of the screen the game is played on.
+    pub(super) screen_dimensions: (u16, u16),
+    /// The area of the terminal the game's characters were last drawn in.
+    pub(super) view_area: Option<Rect>,
+    /// The time at which the game loop last woke up.
+    pub(super) now: Instant,
+    /// The time between the start of the last tick and the start of this one.
+    pub(super) delta_time: Duration,
     /// The chunkmap of the display.
     pub(super) chunks: &'a mut CharChunkMap,
 }
 
 impl<'a> Game<'a> {
     /// Create a game, given a [`CharChunkMap`].
-    pub fn new(chunks: &mut CharChunkMap) -> Game {
+    pub fn new(chunks: &mut CharChunkMap) -> Game<'_> {
         Game {
             should_end: false,
+            paused: false,
+            modal: false,
+            input: None,
+            half_blocks: false,
+            split: None,
+            redraw_requested: false,
+            blinking: HashMap::new(),
+            flashing: HashMap::new(),
+            cursor: None,
+            dirty_rows: HashSet::new(),
+            all_dirty: true,
+            held_key_timeout: None,
+            held_keys: HashMap::new(),
+            key_releases_seen: false,
+            overlays: Vec::new(),
+            hud: Vec::new(),
+            minimap: None,
             message: None,
+            message_queue: VecDeque::new(),
+            status: None,
+            message_scroll: 0,
+            message_shown_at: Instant::now(),
             viewport: ViewportLocation { x: 0, y: 0 },
+            viewport_bounds: None,
+            viewport_scroll: None,
+            shake: None,
+            game_border: Borders::ALL,
+            game_title: None,
+            border_style: None,
+            screen_dimensions: (SCREEN_WIDTH, SCREEN_HEIGHT),
+            view_area: None,
+            now: Instant::now(),
+            delta_time: Duration::ZERO,
             chunks,
         }
     }
@@ -86,13 +368,105 @@ impl<'a> Game<'a> {
     ///
     /// `x` is the width of the screen. `y1` is the height of
     /// the game area; and `y2` is the height of the question area.
+    ///
+    /// The question area is never more than half the height of the screen.
+    /// If a status line is set (see [`Game::set_status`]), the bottom row of
+    /// the screen is kept for it, and isn't counted in either area.
     pub fn screen_size(&self) -> (u16, (u16, u16)) {
+        let (width, height) = self.screen_dimensions;
+        let height = height.saturating_sub(self.status_rows());
         match self.message {
             Some(ref m) => {
-                let rows: u16 = (m.text.matches('\n').count() + 3).try_into().unwrap();
-                (SCREEN_WIDTH, ((SCREEN_HEIGHT - rows), rows))
+                // The message never takes more than half of the screen;
+                // anything that doesn't fit is cut off.
+                let max_rows = height / 2;
+                let rows = u16::try_from(m.text.matches('\n').count().saturating_add(3))
+                    .unwrap_or(u16::MAX)
+                    .min(max_rows);
+                (width, (height.saturating_sub(rows), rows))
             }
-            None => (SCREEN_WIDTH, (SCREEN_HEIGHT, 0)),
+            None => (width, (height, 0)),
+        }
+    }
+
+    /// How many rows the status line takes up.
+    pub(super) fn status_rows(&self) -> u16 {
+        match self.status {
+            Some(_) => 1,
+            None => 0,
+        }
+    }
+
+    /// Set a line of text (like the score, or the player's position) to be
+    /// shown at the very bottom of the screen; or if `status` is None,
+    /// remove it. Unlike a [`Message`], the status line stays until it is
+    /// changed, and is shown whether or not there is a message.
+    ///
+    /// Text which doesn't fit on one line is cut off.
+    pub fn set_status(&mut self, status: Option<String>) {
+        self.status = status;
+    }
+
+    /// Returns the status line, if one is set; see [`Game::set_status`].
+    pub fn get_status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+
+    /// Set the title shown on the game area's border (like the name of
+    /// the current level), or if `title` is None, remove it. This replaces
+    /// any [`crate::GameSettings::game_title`].
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.game_title = title;
+    }
+
+    /// Returns the title shown on the game area's border, if there is one.
+    pub fn get_title(&self) -> Option<&str> {
+        self.game_title.as_deref()
+    }
+
+    /// Set the colours and font of the game area's border and title, or if
+    /// `style` is None, go back to the terminal's default. This replaces any
+    /// [`crate::GameSettings::game_border_style`].
+    pub fn set_border_style(&mut self, style: Option<GameStyle>) {
+        self.border_style = style;
+    }
+
+    /// Creates the [`Block`] drawn around the game area.
+    pub(super) fn game_block(&self) -> Block<'static> {
+        let style = self
+            .border_style
+            .clone()
+            .map(TuiStyle::from)
+            .unwrap_or_default();
+        let block = Block::default()
+            .borders(self.game_border)
+            .border_style(style);
+        match self.game_title {
+            Some(ref title) => block.title(Span::styled(title.clone(), style)),
+            None => block,
+        }
+    }
+
+    /// Get the size of the area in which characters are actually drawn,
+    /// in the form of (width, height). This is the game area from
+    /// [`Game::screen_size`], less the border drawn around it.
+    ///
+    /// This is measured in coordinates, so in half-block mode (see
+    /// [`Game::set_half_blocks`]) the height is doubled.
+    pub(super) fn view_size(&self) -> (u16, u16) {
+        let (width, (main_height, _)) = self.screen_size();
+        let inner = self.game_block().inner(Rect::new(0, 0, width, main_height));
+        (
+            inner.width,
+            inner.height.saturating_mul(self.rows_per_cell()),
+        )
+    }
+
+    /// How many rows of the map are shown in each row of the terminal.
+    pub(super) fn rows_per_cell(&self) -> u16 {
+        match self.half_blocks {
+            true => 2,
+            false => 1,
         }
     }
 
@@ -104,8 +478,100 @@ impl<'a> Game<'a> {
 
     /// Set a new message to be shown; or if `message` is None,
     /// remove any current message.
+    ///
+    /// When the current message is removed, the next message added
+    /// with [`Game::push_message`] (if there is one) is shown instead.
     pub fn set_message(&mut self, message: Option<Message>) {
-        self.message = message;
+        self.modal = false;
+        self.input = None;
+        self.message = message.or_else(|| self.message_queue.pop_front());
+        self.message_scroll = 0;
+        self.message_shown_at = self.now;
+    }
+
+    /// This function takes a mutable reference to a message and swaps
+    /// it with the one currently being shown, like [`Game::swap_chunkmap`].
+    /// This lets you build the next message separately, then show it
+    /// without copying it; and you get back the message that was shown.
+    ///
+    /// Unlike [`Game::set_message`], swapping in `None` doesn't show the
+    /// next message added with [`Game::push_message`].
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, Message};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.set_message(Some(Message::new(String::from("Old"))));
+    /// let mut next = Some(Message::new(String::from("New")));
+    /// game.swap_message(&mut next);
+    /// assert_eq!(next.map(|m| m.text), Some(String::from("Old")));
+    /// ```
+    pub fn swap_message(&mut self, message: &mut Option<Message>) {
+        self.modal = false;
+        self.input = None;
+        std::mem::swap(&mut self.message, message);
+        self.message_scroll = 0;
+        self.message_shown_at = self.now;
+    }
+
+    /// Scroll the current message down by `lines` (or up, if `lines` is
+    /// negative). This lets the user read messages that are too long to
+    /// be shown all at once. Scrolling stops at the start and end of the
+    /// message, and is reset whenever the message changes.
+    pub fn scroll_message(&mut self, lines: i16) {
+        let Some(message) = &self.message else {
+            return;
+        };
+        let (_, (_, msg_height)) = self.screen_size();
+        let total_lines = message.text.lines().count();
+        let max_scroll = total_lines.saturating_sub(usize::from(msg_height.saturating_sub(2)));
+        let max_scroll = u16::try_from(max_scroll).unwrap_or(u16::MAX);
+        self.message_scroll = self
+            .message_scroll
+            .saturating_add_signed(lines)
+            .min(max_scroll);
+    }
+
+    /// Add a message to be shown after every other message. If no
+    /// message is showing, it is shown straight away.
+    ///
+    /// ```rust
+    /// use termgame::{CharChunkMap, Game, Message};
+    /// let mut chunkmap = CharChunkMap::new();
+    /// let mut game = Game::new(&mut chunkmap);
+    /// game.push_message(Message::new(String::from("First")));
+    /// game.push_message(Message::new(String::from("Second")));
+    /// assert_eq!(game.get_message().as_ref().unwrap().text, "First");
+    /// game.set_message(None);
+    /// assert_eq!(game.get_message().as_ref().unwrap().text, "Second");
+    /// ```
+    pub fn push_message(&mut self, message: Message) {
+        if self.message.is_none() {
+            self.set_message(Some(message));
+        } else {
+            self.message_queue.push_back(message);
+        }
+    }
+
+    /// Remove the current message if it has been shown for longer
+    /// than its [`Message::timeout`].
+    pub(super) fn expire_message(&mut self) {
+        let expired = self
+            .message
+            .as_ref()
+            .and_then(|m| m.timeout)
+            .is_some_and(|timeout| self.now.duration_since(self.message_shown_at) >= timeout);
+        if expired {
+            self.set_message(None);
+        }
+    }
+
+    /// Remove the current message, and every message waiting to be shown.
+    pub fn clear_messages(&mut self) {
+        self.modal = false;
+        self.input = None;
+        self.message = None;
+        self.message_queue.clear();
     }
 
     /// Returns `true` if the game is about to end. This
@@ -121,6 +587,468 @@ impl<'a> Game<'a> {
         self.should_end = true;
     }
 
+    /// Pauses or unpauses the game. While the game is paused,
+    /// [`crate::Controller::on_tick`] is not called, but the game is
+    /// still drawn and other event handlers are still called (so that,
+    /// for example, a key can unpause the game).
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Turns half-block mode on or off. In half-block mode, the map is
+    /// treated as a picture, where each character is one square "pixel" of
+    /// colour (its background colour, or if it doesn't have one, its text
+    /// colour), and each row of the terminal shows two rows of the map.
+    /// This is useful for pixel-art games; see [`crate::CharView::half_blocks`].
+    ///
+    /// Everything which works in coordinates (like [`Game::visible_region`]
+    /// and [`Game::screen_to_world`]) takes this into account.
+    pub fn set_half_blocks(&mut self, half_blocks: bool) {
+        if self.half_blocks != half_blocks {
+            self.all_dirty = true;
+        }
+        self.half_blocks = half_blocks;
+    }
+
+    /// Divide the game area in two, showing a second view of the map
+    /// (whose top-left is the given [`ViewportLocation`]) next to the
+    /// usual one; or with `None`, go back to showing just one. This is
+    /// useful for split-screen games, where each player has their own
+    /// camera; see [`crate::CharView::split`].
+    ///
+    /// The second view doesn't move with the viewport, so call this again
+    /// to move it. Everything which works in coordinates (like
+    /// [`Game::screen_to_world`] and the cursor) still only knows about
+    /// the first view.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, SplitDirection, ViewportLocation};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// let player_two = ViewportLocation { x: 100, y: 0 };
+    /// game.set_split(Some((player_two, SplitDirection::Horizontal)));
+    /// ```
+    pub fn set_split(&mut self, split: Option<(ViewportLocation, SplitDirection)>) {
+        if self.split != split {
+            self.all_dirty = true;
+        }
+        self.split = split;
+    }
+
+    /// Returns the second view set by [`Game::set_split`], if there is one.
+    pub fn get_split(&self) -> Option<(ViewportLocation, SplitDirection)> {
+        self.split
+    }
+
+    /// Returns whether the game is in half-block mode; see
+    /// [`Game::set_half_blocks`].
+    pub fn is_half_blocks(&self) -> bool {
+        self.half_blocks
+    }
+
+    /// Returns whether the game is paused; see [`Game::set_paused`].
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Shows `message` until the player presses any key, like a
+    /// "press any key to continue" screen. While it is shown,
+    /// [`crate::Controller::on_tick`] is not called, and the key which
+    /// dismisses it is not passed to [`crate::Controller::on_event`].
+    /// Once it is dismissed, [`crate::Controller::on_modal_closed`] is called.
+    ///
+    /// The message's timeout is ignored.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, Message};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.show_modal(Message::new(String::from("Press any key to start")).title(String::from("Snake")));
+    /// assert!(game.is_showing_modal());
+    /// ```
+    pub fn show_modal(&mut self, mut message: Message) {
+        message.timeout = None;
+        self.set_message(Some(message));
+        self.modal = true;
+    }
+
+    /// Returns whether a message shown with [`Game::show_modal`] is
+    /// still waiting to be dismissed.
+    pub fn is_showing_modal(&self) -> bool {
+        self.modal
+    }
+
+    /// Ask the player to type a line of text (like their name, or a
+    /// command). `prompt` is shown as a message, with what the player has
+    /// typed underneath it. While the prompt is shown, key presses go into
+    /// the text rather than to [`crate::Controller::on_event`]; backspace
+    /// deletes the last character, and enter finishes the text and passes
+    /// it to [`crate::Controller::on_input_complete`].
+    ///
+    /// Showing another message (or calling `set_message(None)` or
+    /// [`Game::clear_messages`]) cancels the prompt.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.prompt(String::from("What is your name?"));
+    /// assert!(game.is_prompting());
+    /// ```
+    pub fn prompt(&mut self, prompt: String) {
+        self.set_message(Some(Message::new(format!("{prompt}\n> "))));
+        self.input = Some((prompt, String::new()));
+    }
+
+    /// Returns whether the game is waiting for the player to finish typing
+    /// the text asked for with [`Game::prompt`].
+    pub fn is_prompting(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// Handles a key typed while a prompt is shown. If it finished the
+    /// text, the prompt is removed and the text is returned.
+    pub(super) fn type_into_prompt(&mut self, key: KeyEvent) -> Option<String> {
+        let (prompt, text) = self.input.as_mut()?;
+        match key.code {
+            KeyCode::Enter => {
+                let text = std::mem::take(text);
+                self.set_message(None);
+                return Some(text);
+            }
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                text.push(c);
+            }
+            _ => return None,
+        }
+        let shown = format!("{prompt}\n> {text}");
+        if let Some(message) = &mut self.message {
+            message.text = shown;
+        }
+        None
+    }
+
+    /// Asks for the game to be drawn again straight away, rather than
+    /// waiting until the next frame is due. This only makes a difference
+    /// when [`crate::GameSettings::render_duration`] is set, and is useful
+    /// for showing the result of a key press instantly.
+    pub fn request_redraw(&mut self) {
+        self.redraw_requested = true;
+    }
+
+    /// Makes the characters at the given coordinates blink: they are shown
+    /// for `interval`, then hidden for `interval`, and so on. Unlike
+    /// [`crate::Font::SLOW_BLINK`], this works in every terminal.
+    ///
+    /// An `interval` of zero stops the coordinates blinking.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game};
+    /// # use std::time::Duration;
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.blink([(3, 4), (4, 4)], Duration::from_millis(500));
+    /// ```
+    pub fn blink(&mut self, coords: impl IntoIterator<Item = (i32, i32)>, interval: Duration) {
+        for coord in coords {
+            if interval.is_zero() {
+                self.blinking.remove(&coord);
+            } else {
+                self.blinking.insert(coord, (interval, self.now));
+            }
+        }
+    }
+
+    /// Stops the characters at the given coordinates blinking.
+    pub fn stop_blinking(&mut self, coords: impl IntoIterator<Item = (i32, i32)>) {
+        for coord in coords {
+            self.blinking.remove(&coord);
+        }
+    }
+
+    /// Highlights the character at `(x, y)` with `style` for the next
+    /// `ticks` ticks (for example, to show the last move made). The style is
+    /// only drawn on top of whatever is at `(x, y)`, and the character
+    /// itself is never changed, so once the ticks are up it looks just as
+    /// it did before. Colours and fonts which `style` leaves as `None` are
+    /// kept from the character underneath.
+    ///
+    /// Flashing the same cell again replaces the old flash, and `ticks` of
+    /// zero stops it. While the game is paused, flashes don't count down.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, GameColor, GameStyle};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.flash(3, 4, GameStyle::new().background_color(Some(GameColor::Yellow)), 10);
+    /// ```
+    pub fn flash(&mut self, x: i32, y: i32, style: GameStyle, ticks: u32) {
+        match ticks {
+            0 => self.flashing.remove(&(x, y)),
+            _ => self.flashing.insert((x, y), (style, ticks)),
+        };
+    }
+
+    /// Counts down every flash by one tick, and removes those which are over.
+    pub(super) fn tick_flashes(&mut self) {
+        self.flashing.retain(|_, (_, ticks)| {
+            *ticks -= 1;
+            *ticks > 0
+        });
+    }
+
+    /// Returns the position on the terminal where `(x, y)` is drawn, if the
+    /// game's characters are drawn in `area` and `(x, y)` is inside it.
+    /// This is the opposite of [`Game::screen_to_world`].
+    pub(super) fn world_to_screen(&self, x: i32, y: i32, area: Rect) -> Option<(u16, u16)> {
+        let viewport = self.drawn_viewport();
+        let col = i64::from(x) - i64::from(viewport.x) + i64::from(area.x);
+        let row = (i64::from(y) - i64::from(viewport.y))
+            .div_euclid(i64::from(self.rows_per_cell()))
+            + i64::from(area.y);
+        let col = u16::try_from(col)
+            .ok()
+            .filter(|col| (area.left()..area.right()).contains(col))?;
+        let row = u16::try_from(row)
+            .ok()
+            .filter(|row| (area.top()..area.bottom()).contains(row))?;
+        Some((col, row))
+    }
+
+    /// The rows which could be on screen. Changes to other rows don't
+    /// need to be drawn until the viewport moves, which redraws everything.
+    /// While the screen shakes, rows up to its intensity away can be shown.
+    fn drawable_rows(&self) -> RangeInclusive<i32> {
+        let (_, height) = self.screen_dimensions;
+        let height = height.saturating_mul(self.rows_per_cell());
+        let shake = self.shake.map_or(0, |(intensity, _, _)| i32::from(intensity));
+        let top = self.viewport.y.saturating_sub(shake);
+        let bottom = self.viewport.y.saturating_add(i32::from(height));
+        top..=bottom.saturating_add(shake)
+    }
+
+    /// Notes that rows `top` to `bottom` (inclusive) have changed, and so
+    /// must be drawn again.
+    fn mark_rows_dirty(&mut self, top: i32, bottom: i32) {
+        let drawable_rows = self.drawable_rows();
+        let top = top.max(*drawable_rows.start());
+        let bottom = bottom.min(*drawable_rows.end());
+        self.dirty_rows.extend(top..=bottom);
+    }
+
+    /// Returns the blinking coordinates which are currently hidden.
+    pub(super) fn hidden_cells(&self) -> HashSet<(i32, i32)> {
+        self.blinking
+            .iter()
+            .filter(|(_, (interval, started))| {
+                let blinks = self.now.duration_since(*started).as_nanos() / interval.as_nanos();
+                blinks % 2 == 1
+            })
+            .map(|(coord, _)| *coord)
+            .collect()
+    }
+
+    /// Shows the terminal's cursor at the given (x, y) coordinates, or hides
+    /// it if `cursor` is `None`. This is useful for text-editor-like games,
+    /// which want to show where the next character will be typed.
+    ///
+    /// The cursor is only shown while its coordinates are visible.
+    /// By default, the cursor is hidden.
+    pub fn set_cursor(&mut self, cursor: Option<(i32, i32)>) {
+        self.cursor = cursor;
+    }
+
+    /// Returns the coordinates the cursor is shown at, if any;
+    /// see [`Game::set_cursor`].
+    pub fn get_cursor(&self) -> Option<(i32, i32)> {
+        self.cursor
+    }
+
+    /// Returns the keys which are currently held down. This is always empty
+    /// unless [`crate::GameSettings::track_held_keys`] was used.
+    pub fn keys_down(&self) -> HashSet<KeyCode> {
+        self.held_keys
+            .keys()
+            .copied()
+            .filter(|key| self.is_key_down(*key))
+            .collect()
+    }
+
+    /// Returns whether `key` is currently held down. This is always `false`
+    /// unless [`crate::GameSettings::track_held_keys`] was used.
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use termgame::{run_game, Controller, Game, GameSettings, KeyCode};
+    ///
+    /// struct MyGame {
+    ///     x: i32,
+    /// }
+    ///
+    /// impl Controller for MyGame {
+    ///     fn on_tick(&mut self, game: &mut Game) {
+    ///         if game.is_key_down(KeyCode::Right) {
+    ///             self.x += 1;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let settings = GameSettings::new().track_held_keys(Some(Duration::from_millis(550)));
+    /// run_game(&mut MyGame { x: 0 }, settings).unwrap();
+    /// ```
+    pub fn is_key_down(&self, key: KeyCode) -> bool {
+        match (self.held_key_timeout, self.held_keys.get(&key)) {
+            (Some(_), Some(_)) if self.key_releases_seen => true,
+            (Some(timeout), Some(pressed)) => self.now.duration_since(*pressed) < timeout,
+            _ => false,
+        }
+    }
+
+    /// Updates which keys are held down, given an event which just happened.
+    pub(super) fn track_held_keys(&mut self, event: &GameEvent) {
+        if self.held_key_timeout.is_none() {
+            return;
+        }
+        match event {
+            GameEvent::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Release,
+                ..
+            }) => {
+                self.key_releases_seen = true;
+                self.held_keys.remove(code);
+            }
+            GameEvent::Key(KeyEvent { code, .. }) => {
+                self.held_keys.insert(*code, self.now);
+            }
+            // Releases can't be seen while the terminal isn't focused.
+            GameEvent::FocusLost => self.held_keys.clear(),
+            _ => {}
+        }
+    }
+
+    /// Draws a tui-rs widget (like a `Gauge` or a `List`) on top of the game, in `area`. The `x` and
+    /// `y` of `area` are counted from the top-left of the game area, and
+    /// anything outside the game area is cut off.
+    ///
+    /// The widget keeps being drawn until it is removed with
+    /// [`Game::remove_overlay`]. Setting an overlay with the same `name`
+    /// as an existing one replaces it; otherwise, later overlays are drawn
+    /// on top of earlier ones.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// use termgame::Rect;
+    /// use tui::widgets::{Block, Borders, Gauge};
+    ///
+    /// let loading = Gauge::default()
+    ///     .block(Block::default().title("Loading").borders(Borders::ALL))
+    ///     .percent(40);
+    /// game.set_overlay("loading", Rect::new(10, 10, 40, 3), loading);
+    /// ```
+    pub fn set_overlay<W: Widget + Clone + 'static>(&mut self, name: &str, area: Rect, widget: W) {
+        let overlay = Overlay {
+            name: name.to_string(),
+            area,
+            render: Box::new(move |area, buf| widget.clone().render(area, buf)),
+        };
+        match self
+            .overlays
+            .iter_mut()
+            .find(|overlay| overlay.name == name)
+        {
+            Some(existing) => *existing = overlay,
+            None => self.overlays.push(overlay),
+        }
+    }
+
+    /// Stops drawing the overlay called `name`; see [`Game::set_overlay`].
+    pub fn remove_overlay(&mut self, name: &str) {
+        self.overlays.retain(|overlay| overlay.name != name);
+    }
+
+    /// Draws a [`Minimap`](crate::Minimap) of the whole map in `area`, with
+    /// a border around it. Each cell of the minimap shows every `scale`th
+    /// coordinate of the map, and the part of the map that is currently in
+    /// view is drawn reversed, in the middle of the minimap.
+    ///
+    /// Like [`Game::set_overlay`], the `x` and `y` of `area` are counted
+    /// from the top-left of the game area. The minimap keeps being drawn
+    /// until [`Game::hide_minimap`] is called.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// use termgame::Rect;
+    /// // A minimap in the top-left corner, showing an area 8 times as
+    /// // wide and tall as itself.
+    /// game.draw_minimap(Rect::new(0, 0, 20, 10), 8);
+    /// ```
+    pub fn draw_minimap(&mut self, area: Rect, scale: u32) {
+        self.minimap = Some((area, scale));
+    }
+
+    /// Stops drawing the minimap; see [`Game::draw_minimap`].
+    pub fn hide_minimap(&mut self) {
+        self.minimap = None;
+    }
+
+    /// Show `text` at a fixed place in the game area (like a score in the
+    /// top-right corner), which stays put when the viewport moves. HUD text
+    /// is drawn on top of the game's characters, but is never added to the
+    /// map.
+    ///
+    /// The text is placed against the edges given by `anchor`, then moved
+    /// `offset` cells (columns, rows) inwards from those edges. Along an
+    /// axis where the text is centred, positive offsets move it right or
+    /// down. Anything outside the game area is cut off.
+    ///
+    /// The text stays until [`Game::clear_hud`] is called. Drawing new text
+    /// with the same `anchor` and `offset` replaces the old text.
+    ///
+    /// ```rust
+    /// # use termgame::{Anchor, CharChunkMap, Game};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.draw_hud_text(Anchor::TopRight, (1, 0), "Score: 100");
+    /// ```
+    pub fn draw_hud_text(&mut self, anchor: Anchor, offset: (i16, i16), text: &str) {
+        let text = text.to_string();
+        match self
+            .hud
+            .iter_mut()
+            .find(|(a, o, _)| (*a, *o) == (anchor, offset))
+        {
+            Some(existing) => existing.2 = text,
+            None => self.hud.push((anchor, offset, text)),
+        }
+    }
+
+    /// Remove all the text drawn with [`Game::draw_hud_text`].
+    pub fn clear_hud(&mut self) {
+        self.hud.clear();
+    }
+
+    /// Returns how much time actually passed between the previous call to
+    /// [`crate::Controller::on_tick`] and the current one. This may be longer
+    /// than the tick duration if the game is running slowly, so use it to
+    /// make movement independent of the frame-rate.
+    ///
+    /// Before the first tick, this is zero.
+    pub fn delta_time(&self) -> Duration {
+        self.delta_time
+    }
+
     /// Return the character at the given (x, y) coordinates.
     ///
     /// If the return is `None`, nothing is at those coordinates.
@@ -130,19 +1058,260 @@ impl<'a> Game<'a> {
         self.chunks.get(x, y).map(|x| StyledCharacter::from(*x))
     }
 
+    /// Return a reference to the character stored at the given (x, y)
+    /// coordinates.
+    ///
+    /// This is like [`Game::get_screen_char`], but avoids converting
+    /// the stored character into a [`StyledCharacter`]; which makes it
+    /// cheaper when scanning many cells (e.g. the whole viewport) every tick.
+    /// The returned value has a `c` field (the character) and a `style` field.
+    pub fn get_screen_char_ref(&self, x: i32, y: i32) -> Option<&ScreenCharacter> {
+        self.chunks.get(x, y)
+    }
+
     /// Place the character at the given (x, y) coordinates.
     ///
     /// If `character` is `None`, remove anything at those coordinates.
     /// If `character` is `Some`, insert [`StyledCharacter`] at those coordinates.
+    ///
+    /// Wide characters (like CJK characters and most emoji) take up two
+    /// columns of the screen, so they hide whatever is at `(x + 1, y)`.
     pub fn set_screen_char(&mut self, x: i32, y: i32, character: Option<StyledCharacter>) {
+        self.mark_rows_dirty(y, y);
         match character {
-            Some(c) => self.chunks.insert(x, y, ScreenCharacter::from(c)),
+            Some(c) => {
+                self.chunks.insert(x, y, ScreenCharacter::from(c));
+            }
             None => {
                 self.chunks.remove(x, y);
             }
         }
     }
 
+    /// Place `character` at the given (x, y) coordinates, but only if
+    /// nothing is there already. Returns whether it was placed.
+    ///
+    /// This is useful for drawing a background behind things which
+    /// have already been drawn.
+    pub fn set_screen_char_if_empty(&mut self, x: i32, y: i32, character: StyledCharacter) -> bool {
+        match self.chunks.entry(x, y) {
+            ChunkMapEntry::Occupied(_) => false,
+            ChunkMapEntry::Vacant(entry) => {
+                entry.insert(character.into());
+                self.mark_rows_dirty(y, y);
+                true
+            }
+        }
+    }
+
+    /// Write `text` onto the screen, starting at `(x, y)`, wrapped so that
+    /// no line is longer than `width` characters. Lines are broken between
+    /// words where possible, and words longer than `width` are split.
+    /// Each line starts at column `x`, one row below the last, and
+    /// newlines in `text` always start a new line.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.draw_text_wrapped(0, 0, 12, "Beware of the dragon beyond this sign.");
+    /// // Beware of
+    /// // the dragon
+    /// // beyond this
+    /// // sign.
+    /// assert_eq!(game.get_screen_char(0, 3).map(|c| c.c), Some('s'));
+    /// ```
+    pub fn draw_text_wrapped(&mut self, x: i32, y: i32, width: u32, text: &str) {
+        let lines = wrap_text(text, usize::try_from(width).unwrap_or(usize::MAX));
+        self.set_many(lines.iter().zip(y..).flat_map(|(line, row)| {
+            line.chars()
+                .zip(x..)
+                .map(move |(c, col)| (col, row, Some(StyledCharacter::new(c))))
+        }));
+    }
+
+    /// Place or remove many characters at once. Each item of `cells` is
+    /// `(x, y, character)`, and is treated as if it was passed to
+    /// [`Game::set_screen_char`]. This is faster than setting each
+    /// character separately, particularly when nearby characters are set
+    /// one after another.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, StyledCharacter};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// let line = "Hello, world!";
+    /// game.set_many(
+    ///     line.chars()
+    ///         .enumerate()
+    ///         .map(|(x, c)| (x as i32, 0, Some(StyledCharacter::new(c)))),
+    /// );
+    /// ```
+    pub fn set_many(
+        &mut self,
+        cells: impl IntoIterator<Item = (i32, i32, Option<StyledCharacter>)>,
+    ) {
+        let drawable_rows = self.drawable_rows();
+        let dirty_rows = &mut self.dirty_rows;
+        self.chunks
+            .set_many(cells.into_iter().map(|(x, y, character)| {
+                if drawable_rows.contains(&y) {
+                    dirty_rows.insert(y);
+                }
+                (x, y, character.map(ScreenCharacter::from))
+            }));
+    }
+
+    /// Copy every character in `sprite` onto the screen, with the sprite's
+    /// `(0, 0)` placed at `(origin_x, origin_y)`. Empty cells of the sprite
+    /// are transparent, leaving whatever is on the screen there untouched.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, StyledCharacter};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// let mut player = CharChunkMap::new();
+    /// player.insert(1, 0, StyledCharacter::new('o').into());
+    /// player.insert(0, 1, StyledCharacter::new('/').into());
+    /// player.insert(1, 1, StyledCharacter::new('|').into());
+    /// player.insert(2, 1, StyledCharacter::new('\\').into());
+    ///
+    /// game.draw_sprite(10, 5, &player);
+    /// assert_eq!(game.get_screen_char(11, 5).map(|c| c.c), Some('o'));
+    /// ```
+    pub fn draw_sprite(&mut self, origin_x: i32, origin_y: i32, sprite: &CharChunkMap) {
+        let drawable_rows = self.drawable_rows();
+        let dirty_rows = &mut self.dirty_rows;
+        self.chunks.extend(sprite.iter().map(|(x, y, character)| {
+            let y = y.saturating_add(origin_y);
+            if drawable_rows.contains(&y) {
+                dirty_rows.insert(y);
+            }
+            (x.saturating_add(origin_x), y, *character)
+        }));
+    }
+
+    /// Change the style of the character at the given (x, y) coordinates,
+    /// without changing the character itself. `restyle` is given the
+    /// character's current style, and returns its new style.
+    ///
+    /// If nothing is at those coordinates, this does nothing.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, GameColor, GameStyle, StyledCharacter};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.set_screen_char(2, 2, Some(StyledCharacter::new('@')));
+    /// game.restyle(2, 2, |style| {
+    ///     Some(style.unwrap_or_default().background_color(Some(GameColor::Yellow)))
+    /// });
+    /// ```
+    pub fn restyle(
+        &mut self,
+        x: i32,
+        y: i32,
+        restyle: impl FnOnce(Option<GameStyle>) -> Option<GameStyle>,
+    ) {
+        if let Some(existing) = self.get_screen_char(x, y) {
+            let character = StyledCharacter {
+                c: existing.c,
+                style: restyle(existing.style),
+            };
+            self.chunks.insert(x, y, character.into());
+            self.mark_rows_dirty(y, y);
+        }
+    }
+
+    /// Draw the outline of a `w` by `h` rectangle, with its top-left
+    /// corner at `(x, y)`, using the character `ch`.
+    ///
+    /// Only the edges are drawn; the inside of the rectangle is left
+    /// untouched. A rectangle with a width or height of `1` is drawn as
+    /// a line (or a single cell), and a zero-sized rectangle draws nothing.
+    ///
+    /// ```rust
+    /// use termgame::{CharChunkMap, Game};
+    /// let mut chunkmap = CharChunkMap::new();
+    /// let mut game = Game::new(&mut chunkmap);
+    /// game.draw_rect(0, 0, 3, 3, '#');
+    /// assert!(game.get_screen_char(0, 0).is_some());
+    /// assert!(game.get_screen_char(1, 1).is_none());
+    /// ```
+    pub fn draw_rect(&mut self, x: i32, y: i32, w: u32, h: u32, ch: char) {
+        if w == 0 || h == 0 {
+            return;
+        }
+        let right = x.saturating_add_unsigned(w - 1);
+        let bottom = y.saturating_add_unsigned(h - 1);
+        self.mark_rows_dirty(y, bottom);
+        for col in x..=right {
+            self.chunks.insert(col, y, ScreenCharacter::from(ch));
+            self.chunks.insert(col, bottom, ScreenCharacter::from(ch));
+        }
+        for row in y..=bottom {
+            self.chunks.insert(x, row, ScreenCharacter::from(ch));
+            self.chunks.insert(right, row, ScreenCharacter::from(ch));
+        }
+    }
+
+    /// Fill every cell of a `w` by `h` rectangle, with its top-left
+    /// corner at `(x, y)`, with the character `ch`.
+    ///
+    /// A zero-sized rectangle draws nothing.
+    pub fn fill_rect(&mut self, x: i32, y: i32, w: u32, h: u32, ch: char) {
+        if w == 0 || h == 0 {
+            return;
+        }
+        let right = x.saturating_add_unsigned(w - 1);
+        let bottom = y.saturating_add_unsigned(h - 1);
+        self.mark_rows_dirty(y, bottom);
+        for row in y..=bottom {
+            for col in x..=right {
+                self.chunks.insert(col, row, ScreenCharacter::from(ch));
+            }
+        }
+    }
+
+    /// Give every cell of a `w` by `h` rectangle, with its top-left corner
+    /// at `(x, y)`, the given `style`. Characters already in the rectangle
+    /// keep their character but take on the new style; empty cells are
+    /// filled with a space. This is useful for coloured bars, panels and
+    /// highlights.
+    ///
+    /// A zero-sized rectangle draws nothing.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, GameColor, GameStyle};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// let health_bar = GameStyle::new().background_color(Some(GameColor::Red));
+    /// game.fill_style_rect(0, 0, 10, 1, health_bar);
+    /// ```
+    pub fn fill_style_rect(&mut self, x: i32, y: i32, w: u32, h: u32, style: GameStyle) {
+        if w == 0 || h == 0 {
+            return;
+        }
+        let right = x.saturating_add_unsigned(w - 1);
+        let bottom = y.saturating_add_unsigned(h - 1);
+        self.mark_rows_dirty(y, bottom);
+        for row in y..=bottom {
+            for col in x..=right {
+                let c = self.chunks.get(col, row).map_or(' ', |existing| existing.c);
+                let character = StyledCharacter::new(c).style(style.clone());
+                self.chunks.insert(col, row, character.into());
+            }
+        }
+    }
+
+    /// Remove every character that has been placed on the screen.
+    ///
+    /// After calling this, [`Game::get_screen_char`] will return `None`
+    /// for every coordinate.
+    pub fn clear(&mut self) {
+        self.all_dirty = true;
+        self.chunks.clear();
+    }
+
     /// This function takes a mutable reference to a chunkmap and
     /// swaps it out for another one. This allows you to do things
     /// like keep multiple maps at once; or do efficient re-builds of
@@ -157,6 +1326,7 @@ impl<'a> Game<'a> {
     /// game.swap_chunkmap(&mut chunkmap2);
     /// ````
     pub fn swap_chunkmap(&mut self, chunkmap: &mut CharChunkMap) {
+        self.all_dirty = true;
         std::mem::swap(self.chunks, chunkmap);
     }
 
@@ -168,7 +1338,836 @@ impl<'a> Game<'a> {
 
     /// This sets the viewport (i.e. the top-left coordniate currently in view)
     /// to the provided [`ViewportLocation`].
+    ///
+    /// If bounds have been set with [`Game::set_viewport_bounds`], the
+    /// viewport will be moved as little as possible to stay within them.
+    ///
+    /// This stops any scrolling started by [`Game::scroll_viewport_to`].
     pub fn set_viewport(&mut self, viewport: ViewportLocation) {
-        self.viewport = viewport;
+        self.viewport_scroll = None;
+        self.place_viewport(viewport);
+    }
+
+    /// Moves the viewport to `viewport`, keeping it within the bounds.
+    fn place_viewport(&mut self, viewport: ViewportLocation) {
+        self.viewport = match self.viewport_bounds {
+            Some((min, max)) => {
+                let (width, height) = self.view_size();
+                let max_x = max.x.saturating_sub(i32::from(width.saturating_sub(1)));
+                let max_y = max.y.saturating_sub(i32::from(height.saturating_sub(1)));
+                ViewportLocation {
+                    x: viewport.x.min(max_x).max(min.x),
+                    y: viewport.y.min(max_y).max(min.y),
+                }
+            }
+            None => viewport,
+        };
+    }
+
+    /// Restrict the viewport so that only coordinates between `min` and `max`
+    /// (inclusive) are ever shown. Any later call to [`Game::set_viewport`]
+    /// or [`Game::move_viewport`] will be clamped, so that neither the top-left
+    /// nor the bottom-right of the view goes past these bounds. If the bounds
+    /// are smaller than the view, the top-left corner is kept at `min`.
+    pub fn set_viewport_bounds(&mut self, min: ViewportLocation, max: ViewportLocation) {
+        self.viewport_bounds = Some((min, max));
+        self.place_viewport(self.viewport);
+    }
+
+    /// Glide the viewport from where it is now to `target`, arriving after
+    /// `over` has passed. The viewport is moved a little further each time
+    /// the screen is drawn, so the camera scrolls smoothly instead of
+    /// jumping. An `over` of zero moves the viewport straight away.
+    ///
+    /// Calling [`Game::set_viewport`] (or anything else which moves the
+    /// viewport) stops the scrolling where it is.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, ViewportLocation};
+    /// # use std::time::Duration;
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.scroll_viewport_to(ViewportLocation { x: 40, y: 0 }, Duration::from_millis(500));
+    /// assert!(game.is_scrolling_viewport());
+    /// ```
+    pub fn scroll_viewport_to(&mut self, target: ViewportLocation, over: Duration) {
+        if over.is_zero() {
+            self.set_viewport(target);
+        } else {
+            self.viewport_scroll = Some((self.viewport, target, self.now, over));
+        }
+    }
+
+    /// Shake the screen, for example when something explodes. While the
+    /// screen shakes, everything is drawn up to `intensity` cells away from
+    /// where it should be, in a different direction each frame. The shaking
+    /// dies down over `duration`, after which everything is drawn exactly
+    /// where it was. The viewport itself (see [`Game::get_viewport`]) never
+    /// changes.
+    ///
+    /// Shaking again replaces any shake which hasn't finished, and an
+    /// `intensity` of zero stops the shaking.
+    pub fn shake(&mut self, intensity: u16, duration: Duration) {
+        self.shake = Some((intensity, self.now, duration)).filter(|_| intensity > 0);
+    }
+
+    /// The viewport the game is drawn with: the viewport, moved by any
+    /// shaking started with [`Game::shake`].
+    pub(super) fn drawn_viewport(&self) -> ViewportLocation {
+        let Some((intensity, started, duration)) = self.shake else {
+            return self.viewport;
+        };
+        let elapsed = self.now.duration_since(started);
+        if elapsed >= duration {
+            return self.viewport;
+        }
+        let remaining = 1.0 - elapsed.as_secs_f64() / duration.as_secs_f64();
+        let reach = (f64::from(intensity) * remaining).round() as u64;
+        // Any number which changes from frame to frame will do.
+        let mut hasher = DefaultHasher::new();
+        elapsed.hash(&mut hasher);
+        let random = hasher.finish();
+        let offset = |bits: u64| (bits % (2 * reach + 1)) as i32 - reach as i32;
+        ViewportLocation {
+            x: self.viewport.x.saturating_add(offset(random)),
+            y: self.viewport.y.saturating_add(offset(random >> 32)),
+        }
+    }
+
+    /// Returns `true` if the viewport is still gliding towards the target
+    /// given to [`Game::scroll_viewport_to`].
+    pub fn is_scrolling_viewport(&self) -> bool {
+        self.viewport_scroll.is_some()
+    }
+
+    /// Moves the viewport along the scroll started by
+    /// [`Game::scroll_viewport_to`], to where it should be at `self.now`.
+    pub(super) fn update_viewport_scroll(&mut self) {
+        let Some((from, to, started, over)) = self.viewport_scroll else {
+            return;
+        };
+        let elapsed = self.now.duration_since(started);
+        if elapsed >= over {
+            self.viewport_scroll = None;
+            self.place_viewport(to);
+            return;
+        }
+        let fraction = (elapsed.as_secs_f64() / over.as_secs_f64()) as f32;
+        self.place_viewport(ViewportLocation {
+            x: lerp(from.x, to.x, fraction),
+            y: lerp(from.y, to.y, fraction),
+        });
+    }
+
+    /// Remove any bounds set by [`Game::set_viewport_bounds`], so that
+    /// the viewport can be moved anywhere.
+    pub fn clear_viewport_bounds(&mut self) {
+        self.viewport_bounds = None;
+    }
+
+    /// Get the coordinates currently shown on screen, in the form
+    /// `(min_x, min_y, max_x, max_y)`. Both corners are inclusive.
+    ///
+    /// If a message is being shown, the rows it takes up are not included.
+    pub fn visible_region(&self) -> (i32, i32, i32, i32) {
+        let (width, height) = self.view_size();
+        (
+            self.viewport.x,
+            self.viewport.y,
+            self.viewport.x.saturating_add(i32::from(width) - 1),
+            self.viewport.y.saturating_add(i32::from(height) - 1),
+        )
+    }
+
+    /// Returns `true` if the character at `(x, y)` is currently shown on screen.
+    pub fn is_visible(&self, x: i32, y: i32) -> bool {
+        let (min_x, min_y, max_x, max_y) = self.visible_region();
+        (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y)
+    }
+
+    /// Returns an iterator over every `(x, y, character)` currently shown
+    /// on screen (that is, inside [`Game::visible_region`]), row by row.
+    /// Only the visible coordinates are looked at, so this stays cheap no
+    /// matter how big the map is.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, StyledCharacter};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// game.set_screen_char(1, 1, Some(StyledCharacter::new('@')));
+    /// game.set_screen_char(-5, 1, Some(StyledCharacter::new('#')));
+    /// let visible: Vec<_> = game.iter_visible().map(|(x, y, ch)| (x, y, ch.c)).collect();
+    /// assert_eq!(visible, vec![(1, 1, '@')]);
+    /// ```
+    pub fn iter_visible(&self) -> impl Iterator<Item = (i32, i32, &ScreenCharacter)> {
+        let (min_x, min_y, max_x, max_y) = self.visible_region();
+        (min_y..=max_y).flat_map(move |y| {
+            (min_x..=max_x).filter_map(move |x| Some((x, y, self.chunks.get(x, y)?)))
+        })
+    }
+
+    /// Convert a position on the terminal (e.g. the `column` and `row` of a
+    /// [`MouseEvent`]) into the (x, y) coordinates of the character shown there.
+    ///
+    /// This accounts for where the game is placed in the terminal, the
+    /// border around it, and the current viewport. If the position is not
+    /// inside the game area (for example, it is on the message, or the
+    /// game has not been drawn yet), this returns `None`. In half-block
+    /// mode, this is the upper of the two coordinates shown at that position.
+    pub fn screen_to_world(&self, col: u16, row: u16) -> Option<(i32, i32)> {
+        let area = self.view_area?;
+        if !(area.left()..area.right()).contains(&col)
+            || !(area.top()..area.bottom()).contains(&row)
+        {
+            return None;
+        }
+        Some((
+            self.viewport.x.saturating_add(i32::from(col - area.left())),
+            self.viewport
+                .y
+                .saturating_add(i32::from(row - area.top()) * i32::from(self.rows_per_cell())),
+        ))
+    }
+
+    /// Find the (x, y) coordinates that a mouse event happened on, and the
+    /// character there (if any); see [`Game::screen_to_world`]. If the
+    /// event is not inside the game area, this returns `None`.
+    ///
+    /// ```rust
+    /// # use termgame::{Controller, Game, GameEvent, StyledCharacter};
+    /// struct Clicker;
+    /// impl Controller for Clicker {
+    ///     fn on_event(&mut self, game: &mut Game, event: GameEvent) {
+    ///         if let GameEvent::Mouse(mouse) = event {
+    ///             if let Some((x, y, Some(_))) = game.hit_test(&mouse) {
+    ///                 // Clicking on a character removes it.
+    ///                 game.set_screen_char(x, y, None);
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn hit_test(&self, event: &MouseEvent) -> Option<(i32, i32, Option<StyledCharacter>)> {
+        let (x, y) = self.screen_to_world(event.column, event.row)?;
+        Some((x, y, self.get_screen_char(x, y)))
+    }
+
+    /// Move the viewport by `dx` columns and `dy` rows. The viewport
+    /// stops at the edges of the coordinate space rather than overflowing.
+    pub fn move_viewport(&mut self, dx: i32, dy: i32) {
+        self.set_viewport(ViewportLocation {
+            x: self.viewport.x.saturating_add(dx),
+            y: self.viewport.y.saturating_add(dy),
+        });
+    }
+
+    /// Move the viewport so that `(x, y)` is as close to the center
+    /// of the game area as possible. If a message is being shown, the
+    /// rows it takes up are not counted as part of the game area.
+    pub fn center_viewport_on(&mut self, x: i32, y: i32) {
+        let (width, height) = self.view_size();
+        self.set_viewport(ViewportLocation {
+            x: x.saturating_sub(i32::from(width / 2)),
+            y: y.saturating_sub(i32::from(height / 2)),
+        });
+    }
+
+    /// Move the viewport as little as possible to keep `(x, y)` at least
+    /// `margin` cells away from the edges of the game area. While `(x, y)`
+    /// stays inside that box (the "dead zone"), the viewport doesn't move,
+    /// which makes a camera following the player feel much calmer than
+    /// [`Game::center_viewport_on`].
+    ///
+    /// A `margin` of more than half the game area is treated as half of
+    /// it, which keeps `(x, y)` in the middle. The viewport bounds set by
+    /// [`Game::set_viewport_bounds`] are still respected.
+    ///
+    /// ```rust
+    /// # use termgame::{CharChunkMap, Game, ViewportLocation};
+    /// # let mut chunks = CharChunkMap::new();
+    /// # let mut game = Game::new(&mut chunks);
+    /// // The game area is 78 cells wide, so this moves the viewport.
+    /// game.follow(80, 5, 4);
+    /// assert_eq!(game.get_viewport(), ViewportLocation { x: 7, y: 0 });
+    /// // But this is still inside the dead zone.
+    /// game.follow(70, 6, 4);
+    /// assert_eq!(game.get_viewport(), ViewportLocation { x: 7, y: 0 });
+    /// ```
+    pub fn follow(&mut self, x: i32, y: i32, margin: u16) {
+        let (width, height) = self.view_size();
+        // The new top-left (or left) coordinate along one axis.
+        let follow_axis = |start: i32, size: u16, target: i32| {
+            let margin = i64::from(margin.min(size.saturating_sub(1) / 2));
+            let (start, size, target) = (i64::from(start), i64::from(size), i64::from(target));
+            let start = if target < start + margin {
+                target - margin
+            } else if target > start + size - 1 - margin {
+                target - (size - 1 - margin)
+            } else {
+                start
+            };
+            start.clamp(i32::MIN.into(), i32::MAX.into()) as i32
+        };
+        let viewport = ViewportLocation {
+            x: follow_axis(self.viewport.x, width, x),
+            y: follow_axis(self.viewport.y, height, y),
+        };
+        if viewport != self.viewport {
+            self.set_viewport(viewport);
+        }
+    }
+
+    /// Returns the coordinates shown in the middle of the game area. This
+    /// is the inverse of [`Game::center_viewport_on`]; so after calling
+    /// `center_viewport_on(x, y)`, this returns `(x, y)` (unless the
+    /// viewport bounds stopped the viewport from moving there).
+    pub fn center_coordinate(&self) -> (i32, i32) {
+        let (width, height) = self.view_size();
+        (
+            self.viewport.x.saturating_add(i32::from(width / 2)),
+            self.viewport.y.saturating_add(i32::from(height / 2)),
+        )
+    }
+
+    /// Returns the character shown in the middle of the game area;
+    /// see [`Game::center_coordinate`].
+    pub fn char_at_center(&self) -> Option<StyledCharacter> {
+        let (x, y) = self.center_coordinate();
+        self.get_screen_char(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CharChunkMap, Color, Game, GameEvent, GameStyle, KeyCode, KeyEvent, KeyEventKind,
+        KeyEventState, KeyModifiers, Message, MouseButton, MouseEvent, MouseEventKind, Rect,
+        SimpleEvent, StyledCharacter, ViewportLocation, SCREEN_HEIGHT, SCREEN_WIDTH,
+    };
+    use std::time::Duration;
+    use tui::{
+        buffer::Buffer,
+        widgets::{Borders, Widget},
+    };
+
+    #[test]
+    fn draw_rect_outline_only() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.draw_rect(-1, -1, 4, 3, '#');
+        for x in -1..3 {
+            assert!(game.get_screen_char(x, -1).is_some());
+            assert!(game.get_screen_char(x, 1).is_some());
+        }
+        assert!(game.get_screen_char(-1, 0).is_some());
+        assert!(game.get_screen_char(2, 0).is_some());
+        assert!(game.get_screen_char(0, 0).is_none());
+        assert!(game.get_screen_char(1, 0).is_none());
+        assert!(game.get_screen_char(3, 0).is_none());
+    }
+
+    #[test]
+    fn draw_rect_degenerate() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.draw_rect(0, 0, 0, 5, '#');
+        game.draw_rect(0, 0, 5, 0, '#');
+        assert!(game.get_screen_char(0, 0).is_none());
+
+        game.draw_rect(0, 0, 1, 1, '#');
+        assert!(game.get_screen_char(0, 0).is_some());
+        assert!(game.get_screen_char(1, 0).is_none());
+        assert!(game.get_screen_char(0, 1).is_none());
+
+        game.draw_rect(5, 5, 1, 3, '|');
+        for y in 5..8 {
+            assert!(game.get_screen_char(5, y).is_some());
+        }
+        assert!(game.get_screen_char(5, 8).is_none());
+    }
+
+    #[test]
+    fn center_viewport_accounts_for_message() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.center_viewport_on(100, 100);
+        let (width, (height, _)) = game.screen_size();
+        let viewport = game.get_viewport();
+        assert_eq!(viewport.x, 100 - i32::from((width - 2) / 2));
+        assert_eq!(viewport.y, 100 - i32::from((height - 2) / 2));
+
+        game.set_message(Some(Message::new(String::from("Hello"))));
+        game.center_viewport_on(100, 100);
+        let (_, (height, _)) = game.screen_size();
+        assert_eq!(game.get_viewport().y, 100 - i32::from((height - 2) / 2));
+    }
+
+    #[test]
+    fn move_viewport_saturates() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.move_viewport(3, -2);
+        assert_eq!((game.get_viewport().x, game.get_viewport().y), (3, -2));
+        game.move_viewport(i32::MAX, i32::MIN);
+        assert_eq!(
+            (game.get_viewport().x, game.get_viewport().y),
+            (i32::MAX, i32::MIN)
+        );
+    }
+
+    #[test]
+    fn viewport_bounds_clamp_far_edge() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        let (width, height) = game.view_size();
+        game.set_viewport_bounds(
+            ViewportLocation { x: 0, y: 0 },
+            ViewportLocation { x: 199, y: 99 },
+        );
+        game.move_viewport(-5, -5);
+        assert_eq!((game.get_viewport().x, game.get_viewport().y), (0, 0));
+        game.move_viewport(1000, 1000);
+        assert_eq!(game.get_viewport().x, 200 - i32::from(width));
+        assert_eq!(game.get_viewport().y, 100 - i32::from(height));
+
+        game.clear_viewport_bounds();
+        game.set_viewport(ViewportLocation { x: -5, y: -5 });
+        assert_eq!((game.get_viewport().x, game.get_viewport().y), (-5, -5));
+    }
+
+    #[test]
+    fn visible_region_shrinks_with_message() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_viewport(ViewportLocation { x: 10, y: 20 });
+        let (min_x, min_y, max_x, max_y) = game.visible_region();
+        assert_eq!((min_x, min_y), (10, 20));
+        assert!(game.is_visible(max_x, max_y));
+        assert!(!game.is_visible(max_x + 1, max_y));
+        assert!(!game.is_visible(9, 20));
+
+        game.set_message(Some(Message::new(String::from("Hello"))));
+        let (_, _, _, shorter_max_y) = game.visible_region();
+        assert_eq!(shorter_max_y, max_y - 3);
+        assert!(!game.is_visible(max_x, max_y));
+    }
+
+    #[test]
+    fn simple_event_shift() {
+        let event = GameEvent::Key(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT));
+        assert_eq!(
+            SimpleEvent::from(event),
+            SimpleEvent::WithShift(KeyCode::BackTab)
+        );
+
+        let event = GameEvent::Key(KeyEvent::new(
+            KeyCode::Left,
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        ));
+        assert_eq!(
+            SimpleEvent::from(event.clone()),
+            SimpleEvent::WithControlShift(KeyCode::Left)
+        );
+        assert_eq!(
+            GameEvent::from(SimpleEvent::WithControlShift(KeyCode::Left)),
+            event
+        );
+
+        let event = GameEvent::Key(KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT));
+        assert_eq!(
+            SimpleEvent::from(event),
+            SimpleEvent::Just(KeyCode::Char('A'))
+        );
+    }
+
+    #[test]
+    fn simple_event_unusual_modifiers() {
+        let event = GameEvent::Key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::all()));
+        assert_eq!(
+            SimpleEvent::from(event),
+            SimpleEvent::WithControlAlt(KeyCode::Char('x'))
+        );
+        let event = GameEvent::Key(KeyEvent::new(KeyCode::Up, KeyModifiers::all()));
+        assert_eq!(
+            SimpleEvent::from(event),
+            SimpleEvent::WithControlAltShift(KeyCode::Up)
+        );
+    }
+
+    #[test]
+    fn simple_event_key_release_is_complex() {
+        let press = KeyEvent::new(KeyCode::Left, KeyModifiers::NONE);
+        let release =
+            KeyEvent::new_with_kind(KeyCode::Left, KeyModifiers::NONE, KeyEventKind::Release);
+        let repeat =
+            KeyEvent::new_with_kind(KeyCode::Left, KeyModifiers::NONE, KeyEventKind::Repeat);
+        let just_left: Vec<_> = [press, release, repeat]
+            .into_iter()
+            .map(|key| SimpleEvent::from(GameEvent::Key(key)))
+            .filter(|event| *event == SimpleEvent::Just(KeyCode::Left))
+            .collect();
+        // The press and the repeat, but not the release.
+        assert_eq!(just_left.len(), 2);
+    }
+
+    #[test]
+    fn simple_event_mouse() {
+        let event = GameEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 4,
+            row: 7,
+            modifiers: KeyModifiers::SHIFT,
+        });
+        assert_eq!(
+            SimpleEvent::from(event),
+            SimpleEvent::LeftClick { x: 4, y: 7 }
+        );
+        let scroll = SimpleEvent::Scroll {
+            x: 1,
+            y: 2,
+            lines: -1,
+        };
+        assert_eq!(SimpleEvent::from(GameEvent::from(scroll.clone())), scroll);
+
+        let event = GameEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::Moved,
+            column: 4,
+            row: 7,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert!(matches!(
+            SimpleEvent::from(event),
+            SimpleEvent::ComplexEvent(_)
+        ));
+    }
+
+    #[test]
+    fn screen_to_world_uses_drawn_area() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        assert_eq!(game.screen_to_world(10, 10), None);
+
+        game.view_area = Some(Rect::new(11, 6, 78, 22));
+        game.set_viewport(ViewportLocation { x: -5, y: 100 });
+        assert_eq!(game.screen_to_world(11, 6), Some((-5, 100)));
+        assert_eq!(game.screen_to_world(88, 27), Some((72, 121)));
+        assert_eq!(game.screen_to_world(10, 6), None);
+        assert_eq!(game.screen_to_world(89, 6), None);
+        assert_eq!(game.screen_to_world(11, 28), None);
+    }
+
+    #[test]
+    fn hit_test_finds_clicked_character() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.view_area = Some(Rect::new(1, 1, 78, 22));
+        game.set_screen_char(2, 3, Some(StyledCharacter::new('@')));
+        let click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        assert_eq!(
+            game.hit_test(&click(3, 4)),
+            Some((2, 3, Some(StyledCharacter::new('@'))))
+        );
+        assert_eq!(game.hit_test(&click(4, 4)), Some((3, 3, None)));
+        assert_eq!(game.hit_test(&click(0, 4)), None);
+    }
+
+    #[test]
+    fn message_timeout_shows_next() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.push_message(Message::new(String::from("First")).timeout(Duration::ZERO));
+        game.push_message(Message::new(String::from("Second")).timeout(Duration::from_secs(60)));
+        game.expire_message();
+        assert_eq!(game.get_message().as_ref().unwrap().text, "Second");
+        game.expire_message();
+        assert_eq!(game.get_message().as_ref().unwrap().text, "Second");
+    }
+
+    #[test]
+    fn clear_messages_closes_modal() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.show_modal(Message::new(String::from("Press any key")));
+        game.clear_messages();
+        assert!(!game.is_showing_modal());
+        assert!(game.get_message().is_none());
+    }
+
+    #[test]
+    fn clear_messages_cancels_prompt() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.prompt(String::from("What is your name?"));
+        game.clear_messages();
+        assert!(!game.is_prompting());
+        assert!(game.get_message().is_none());
+    }
+
+    #[test]
+    fn screen_size_with_tall_message() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_message(Some(Message::new("line\n".repeat(30))));
+        let (_, (main_height, msg_height)) = game.screen_size();
+        assert_eq!(main_height + msg_height, SCREEN_HEIGHT);
+        assert_eq!(msg_height, SCREEN_HEIGHT / 2);
+    }
+
+    #[test]
+    fn scroll_message_is_clamped() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_message(Some(Message::new("line\n".repeat(30))));
+        let (_, (_, msg_height)) = game.screen_size();
+        game.scroll_message(-3);
+        assert_eq!(game.message_scroll, 0);
+        game.scroll_message(5);
+        assert_eq!(game.message_scroll, 5);
+        game.scroll_message(i16::MAX);
+        assert_eq!(game.message_scroll, 30 - (msg_height - 2));
+
+        game.set_message(Some(Message::new(String::from("Short"))));
+        assert_eq!(game.message_scroll, 0);
+        game.scroll_message(1);
+        assert_eq!(game.message_scroll, 0);
+    }
+
+    #[test]
+    fn follow_has_dead_zone_and_clamps() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        // The game area is 78x22; with a margin of 5, anything from (5, 5)
+        // to (72, 16) is inside the dead zone.
+        game.follow(72, 16, 5);
+        game.follow(5, 5, 5);
+        assert_eq!(game.get_viewport(), ViewportLocation { x: 0, y: 0 });
+        game.follow(73, 4, 5);
+        assert_eq!(game.get_viewport(), ViewportLocation { x: 1, y: -1 });
+
+        // A huge margin keeps the point in the middle.
+        game.follow(100, 100, u16::MAX);
+        assert_eq!(game.center_coordinate(), (100, 100));
+
+        // The viewport stays inside its bounds.
+        game.set_viewport_bounds(
+            ViewportLocation { x: 0, y: 0 },
+            ViewportLocation { x: 99, y: 99 },
+        );
+        game.follow(200, -50, 5);
+        assert_eq!(game.get_viewport(), ViewportLocation { x: 22, y: 0 });
+    }
+
+    #[test]
+    fn view_size_without_border() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        assert_eq!(game.view_size(), (SCREEN_WIDTH - 2, SCREEN_HEIGHT - 2));
+        game.game_border = Borders::NONE;
+        assert_eq!(game.view_size(), (SCREEN_WIDTH, SCREEN_HEIGHT));
+        game.game_title = Some(String::from("Level 1"));
+        assert_eq!(game.view_size(), (SCREEN_WIDTH, SCREEN_HEIGHT - 1));
+    }
+
+    #[test]
+    fn fill_rect_fills_interior() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.fill_rect(0, 0, 3, 2, '.');
+        for y in 0..2 {
+            for x in 0..3 {
+                assert!(game.get_screen_char(x, y).is_some());
+            }
+        }
+        assert!(game.get_screen_char(3, 0).is_none());
+        assert!(game.get_screen_char(0, 2).is_none());
+    }
+
+    #[test]
+    fn fill_style_rect_keeps_characters() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_screen_char(1, 0, Some(StyledCharacter::new('x')));
+        let style = GameStyle::new().background_color(Some(Color::Red));
+        game.fill_style_rect(0, 0, 2, 1, style.clone());
+
+        let expected = |c| Some(StyledCharacter::new(c).style(style.clone()));
+        assert_eq!(game.get_screen_char(0, 0), expected(' '));
+        assert_eq!(game.get_screen_char(1, 0), expected('x'));
+        assert_eq!(game.get_screen_char(2, 0), None);
+    }
+
+    #[test]
+    fn restyle_keeps_character() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_screen_char(0, 0, Some(StyledCharacter::new('@')));
+        let style = GameStyle::new().color(Some(Color::Green));
+        game.restyle(0, 0, |_| Some(style.clone()));
+        game.restyle(1, 0, |_| Some(style.clone()));
+
+        assert_eq!(
+            game.get_screen_char(0, 0),
+            Some(StyledCharacter::new('@').style(style))
+        );
+        assert_eq!(game.get_screen_char(1, 0), None);
+    }
+
+    #[test]
+    fn set_screen_char_if_empty_keeps_existing() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        assert!(game.set_screen_char_if_empty(0, 0, StyledCharacter::new('@')));
+        assert!(!game.set_screen_char_if_empty(0, 0, StyledCharacter::new('.')));
+        assert_eq!(game.get_screen_char(0, 0), Some(StyledCharacter::new('@')));
+    }
+
+    #[test]
+    fn held_keys_time_out() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        let press = SimpleEvent::Just(KeyCode::Left).into();
+        game.track_held_keys(&press);
+        assert!(!game.is_key_down(KeyCode::Left));
+
+        game.held_key_timeout = Some(Duration::from_millis(500));
+        game.track_held_keys(&press);
+        assert!(game.is_key_down(KeyCode::Left));
+        assert_eq!(game.keys_down(), [KeyCode::Left].into());
+        game.now += Duration::from_millis(500);
+        assert!(!game.is_key_down(KeyCode::Left));
+    }
+
+    #[test]
+    fn held_keys_released() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.held_key_timeout = Some(Duration::from_millis(500));
+        let event = |code, kind| {
+            GameEvent::Key(KeyEvent {
+                code,
+                modifiers: KeyModifiers::NONE,
+                kind,
+                state: KeyEventState::NONE,
+            })
+        };
+        game.track_held_keys(&event(KeyCode::Up, KeyEventKind::Press));
+        game.track_held_keys(&event(KeyCode::Down, KeyEventKind::Press));
+        game.track_held_keys(&event(KeyCode::Down, KeyEventKind::Release));
+        game.now += Duration::from_secs(5);
+        // Once releases have been seen, keys are held until released.
+        assert_eq!(game.keys_down(), [KeyCode::Up].into());
+    }
+
+    #[test]
+    fn wrap_text_breaks_words() {
+        assert_eq!(
+            super::wrap_text("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(
+            super::wrap_text("a supercalifragilistic word", 8),
+            vec!["a", "supercal", "ifragili", "stic", "word"]
+        );
+        assert_eq!(super::wrap_text("one\n\ntwo", 10), vec!["one", "", "two"]);
+        assert!(super::wrap_text("anything", 0).is_empty());
+    }
+
+    #[test]
+    fn center_coordinate_matches_center_viewport_on() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_screen_char(-7, 300, Some(StyledCharacter::new('@')));
+        game.center_viewport_on(-7, 300);
+        assert_eq!(game.center_coordinate(), (-7, 300));
+        assert_eq!(game.char_at_center(), Some(StyledCharacter::new('@')));
+    }
+
+    #[test]
+    fn check_blink() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.blink([(1, 1), (2, 2)], Duration::from_millis(100));
+        assert!(game.hidden_cells().is_empty());
+
+        game.now += Duration::from_millis(150);
+        assert_eq!(game.hidden_cells(), [(1, 1), (2, 2)].into());
+        game.stop_blinking([(2, 2)]);
+        assert_eq!(game.hidden_cells(), [(1, 1)].into());
+
+        game.now += Duration::from_millis(100);
+        assert!(game.hidden_cells().is_empty());
+    }
+
+    #[test]
+    fn check_scroll_viewport_to() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        let target = ViewportLocation { x: 40, y: -20 };
+        game.scroll_viewport_to(target, Duration::from_millis(400));
+
+        game.now += Duration::from_millis(100);
+        game.update_viewport_scroll();
+        assert_eq!(game.get_viewport(), ViewportLocation { x: 10, y: -5 });
+
+        game.now += Duration::from_millis(400);
+        game.update_viewport_scroll();
+        assert_eq!(game.get_viewport(), target);
+        assert!(!game.is_scrolling_viewport());
+
+        game.scroll_viewport_to(ViewportLocation { x: 0, y: 0 }, Duration::from_secs(1));
+        game.move_viewport(1, 1);
+        assert!(!game.is_scrolling_viewport());
+        assert_eq!(game.get_viewport(), ViewportLocation { x: 41, y: -19 });
+    }
+
+    #[test]
+    fn border_title_and_style() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_title(Some(String::from("Level 2")));
+        game.set_border_style(Some(GameStyle::new().color(Some(Color::Yellow))));
+        assert_eq!(game.get_title(), Some("Level 2"));
+
+        let area = Rect::new(0, 0, 12, 3);
+        let mut buf = Buffer::empty(area);
+        game.game_block().render(area, &mut buf);
+        assert_eq!(buf.get(1, 0).symbol, "L");
+        assert_eq!(buf.get(1, 0).fg, Color::Yellow);
+        assert_eq!(buf.get(0, 1).fg, Color::Yellow);
+    }
+
+    #[test]
+    fn shake_dies_down() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_viewport(ViewportLocation { x: 10, y: 10 });
+        game.shake(3, Duration::from_millis(100));
+        let mut moved = false;
+        for _ in 0..10 {
+            let drawn = game.drawn_viewport();
+            assert!((7..=13).contains(&drawn.x) && (7..=13).contains(&drawn.y));
+            moved |= drawn != game.get_viewport();
+            game.now += Duration::from_millis(10);
+        }
+        assert!(moved);
+        assert_eq!(game.drawn_viewport(), ViewportLocation { x: 10, y: 10 });
+        assert_eq!(game.get_viewport(), ViewportLocation { x: 10, y: 10 });
+    }
+
+    #[test]
+    fn shake_marks_rows_it_can_show_dirty() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_viewport(ViewportLocation { x: 0, y: 10 });
+        game.shake(5, Duration::from_millis(100));
+        game.set_screen_char(0, 5, Some(StyledCharacter::new('x')));
+        game.set_screen_char(0, 4, Some(StyledCharacter::new('y')));
+        assert!(game.dirty_rows.contains(&5));
+        assert!(!game.dirty_rows.contains(&4));
     }
 }
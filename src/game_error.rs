@@ -2,6 +2,13 @@
 #[derive(Debug)]
 pub enum GameError {
     /// An error occured while running the application.
+    ///
+    /// termgame no longer returns this; errors while running the game are
+    /// now [`GameError::Drawing`], [`GameError::ReadingEvents`] or
+    /// [`GameError::Recording`].
+    #[deprecated(
+        note = "use GameError::Drawing, GameError::ReadingEvents or GameError::Recording"
+    )]
     Running(std::io::Error),
     /// An error occured trying to enable RawMode on
     /// this terminal.
@@ -10,12 +17,57 @@ pub enum GameError {
     TerminalMode(std::io::Error),
     /// An error occured trying to execute commands on the terminal.
     TerminalExecute(std::io::Error),
+    /// An error occured while drawing the game to the terminal.
+    Drawing(std::io::Error),
+    /// An error occured while waiting for, or reading, an event.
+    ReadingEvents(std::io::Error),
+    /// An error occured while recording events to a file
+    /// (see [`crate::GameSettings::record_to`]).
+    Recording(std::io::Error),
 }
 
-impl std::error::Error for GameError {}
+impl std::error::Error for GameError {
+    #[allow(deprecated)]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GameError::RawMode(e) => Some(e.as_ref()),
+            GameError::Running(e)
+            | GameError::TerminalMode(e)
+            | GameError::TerminalExecute(e)
+            | GameError::Drawing(e)
+            | GameError::ReadingEvents(e)
+            | GameError::Recording(e) => Some(e),
+        }
+    }
+}
 
 impl std::fmt::Display for GameError {
+    #[allow(deprecated)]
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "Game Encountered an Error: {:?}", self) // user-facing output
+        // user-facing output
+        match self {
+            GameError::Running(e) => write!(f, "The game stopped because of an error: {e}"),
+            GameError::RawMode(e) => write!(f, "Failed to enable raw mode: {e}"),
+            GameError::TerminalMode(e) => write!(f, "Failed to set up the terminal: {e}"),
+            GameError::TerminalExecute(e) => {
+                write!(f, "Failed to send a command to the terminal: {e}")
+            }
+            GameError::Drawing(e) => write!(f, "Failed to draw the game: {e}"),
+            GameError::ReadingEvents(e) => write!(f, "Failed to read input: {e}"),
+            GameError::Recording(e) => write!(f, "Failed to record input: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GameError;
+    use std::{error::Error, io};
+
+    #[test]
+    fn display_is_readable() {
+        let error = GameError::Drawing(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"));
+        assert_eq!(error.to_string(), "Failed to draw the game: pipe closed");
+        assert_eq!(error.source().unwrap().to_string(), "pipe closed");
     }
 }
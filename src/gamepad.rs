@@ -0,0 +1,89 @@
+use gilrs::{EventType, Gilrs};
+
+pub use gilrs::{Axis as GamepadAxis, Button as GamepadButton};
+
+/// Something that happened on a gamepad; see
+/// [`Controller::on_gamepad_event`](crate::Controller::on_gamepad_event).
+///
+/// Every event says which gamepad it happened on, as a number which stays
+/// the same while that gamepad is connected. This lets each player use
+/// their own gamepad.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadEvent {
+    /// A gamepad was plugged in (or was already plugged in when the game
+    /// started).
+    Connected(usize),
+    /// A gamepad was unplugged.
+    Disconnected(usize),
+    /// A button was pressed.
+    Pressed(usize, GamepadButton),
+    /// A button which was pressed has been released.
+    Released(usize, GamepadButton),
+    /// A stick (or other axis) moved. The value is between `-1.0` and
+    /// `1.0`; for sticks, positive values are right and up.
+    Axis(usize, GamepadAxis, f32),
+}
+
+impl GamepadEvent {
+    /// The event for `event` happening on `gamepad`, if termgame gives
+    /// games that kind of event.
+    fn from_gilrs(gamepad: usize, event: EventType) -> Option<GamepadEvent> {
+        match event {
+            EventType::Connected => Some(GamepadEvent::Connected(gamepad)),
+            EventType::Disconnected => Some(GamepadEvent::Disconnected(gamepad)),
+            EventType::ButtonPressed(button, _) => Some(GamepadEvent::Pressed(gamepad, button)),
+            EventType::ButtonReleased(button, _) => Some(GamepadEvent::Released(gamepad, button)),
+            EventType::AxisChanged(axis, value, _) => {
+                Some(GamepadEvent::Axis(gamepad, axis, value))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reads events from every connected gamepad. If gamepads can't be read
+/// (for example, because there is no gamepad driver), there are never any
+/// events.
+pub(crate) struct Gamepads(Option<Gilrs>);
+
+impl Gamepads {
+    /// Starts reading events from gamepads.
+    pub(crate) fn new() -> Gamepads {
+        Gamepads(Gilrs::new().ok())
+    }
+
+    /// Returns the next event from any gamepad, without waiting.
+    pub(crate) fn next_event(&mut self) -> Option<GamepadEvent> {
+        let gilrs = self.0.as_mut()?;
+        while let Some(event) = gilrs.next_event() {
+            if let Some(event) = GamepadEvent::from_gilrs(event.id.into(), event.event) {
+                return Some(event);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GamepadEvent, Gamepads};
+    use gilrs::EventType;
+
+    #[test]
+    fn only_some_events_are_given_to_games() {
+        assert_eq!(
+            GamepadEvent::from_gilrs(1, EventType::Connected),
+            Some(GamepadEvent::Connected(1))
+        );
+        assert_eq!(
+            GamepadEvent::from_gilrs(2, EventType::Disconnected),
+            Some(GamepadEvent::Disconnected(2))
+        );
+        assert_eq!(GamepadEvent::from_gilrs(1, EventType::Dropped), None);
+    }
+
+    #[test]
+    fn missing_gamepad_driver_gives_no_events() {
+        assert_eq!(Gamepads(None).next_event(), None);
+    }
+}
@@ -0,0 +1,63 @@
+use tui::layout::Rect;
+
+/// Which part of the game area some HUD text is fixed to; see
+/// [`Game::draw_hud_text`](crate::Game::draw_hud_text).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    /// The top-left corner.
+    TopLeft,
+    /// The middle of the top edge.
+    Top,
+    /// The top-right corner.
+    TopRight,
+    /// The middle of the left edge.
+    Left,
+    /// The middle of the game area.
+    Center,
+    /// The middle of the right edge.
+    Right,
+    /// The bottom-left corner.
+    BottomLeft,
+    /// The middle of the bottom edge.
+    Bottom,
+    /// The bottom-right corner.
+    BottomRight,
+}
+
+impl Anchor {
+    /// Where text `width` cells wide starts, when it is anchored inside
+    /// `area` and moved by `offset` (inwards from the edges it is anchored
+    /// to; or right and down, along an axis where it is centred).
+    pub(crate) fn position(self, area: Rect, width: u16, offset: (i16, i16)) -> (i64, i64) {
+        let (dx, dy) = (i64::from(offset.0), i64::from(offset.1));
+        let (left, top) = (i64::from(area.left()), i64::from(area.top()));
+        let (right, bottom) = (i64::from(area.right()), i64::from(area.bottom()));
+        let width = i64::from(width);
+        let x = match self {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => left + dx,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => left + (right - left - width) / 2 + dx,
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => right - width - dx,
+        };
+        let y = match self {
+            Anchor::TopLeft | Anchor::Top | Anchor::TopRight => top + dy,
+            Anchor::Left | Anchor::Center | Anchor::Right => top + (bottom - top - 1) / 2 + dy,
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => bottom - 1 - dy,
+        };
+        (x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Anchor;
+    use tui::layout::Rect;
+
+    #[test]
+    fn anchors_are_inside_area() {
+        let area = Rect::new(1, 1, 20, 10);
+        assert_eq!(Anchor::TopLeft.position(area, 5, (0, 0)), (1, 1));
+        assert_eq!(Anchor::TopRight.position(area, 5, (1, 0)), (15, 1));
+        assert_eq!(Anchor::Center.position(area, 4, (0, 0)), (9, 5));
+        assert_eq!(Anchor::BottomRight.position(area, 5, (0, 2)), (16, 8));
+    }
+}
@@ -10,6 +10,26 @@
 //! It also wraps many tui features, like [`StyledCharacter`],
 //! [`GameEvent`], and [`GameStyle`]
 //!
+//! With the `gamepad` feature, games can also be played with a gamepad;
+//! see `Controller::on_gamepad_event`.
+//!
+//! # Held keys
+//!
+//! Most terminals only report key presses, and repeat a held key after a
+//! pause. [`GameSettings::track_held_keys`] treats a key as held until its
+//! timeout passes without another press; a timeout a little longer than
+//! the key-repeat delay (often 500ms) works well.
+//! [`GameSettings::key_repeat`] repeats presses without the pause, and
+//! stops once the terminal hasn't repeated the key for that timeout (or
+//! 600ms if keys aren't tracked).
+//!
+//! With [`GameSettings::keyboard_enhancement`], terminals which support the
+//! [kitty keyboard protocol](https://sw.kovidgoyal.net/kitty/keyboard-protocol/)
+//! also report releases and repeats, as key events whose `kind` is
+//! [`KeyEventKind::Release`] or [`KeyEventKind::Repeat`]. These are used
+//! instead of the timeouts. Releases never become a [`SimpleEvent::Just`],
+//! so games matching on [`SimpleEvent`]s don't act twice for each press.
+//!
 //! ```no_run
 //!
 //! use termgame::{SimpleEvent, Controller, Game, GameEvent, GameSettings, StyledCharacter, run_game, KeyCode};
@@ -19,9 +39,6 @@
 //! struct MyGame {}
 //!
 //! impl Controller for MyGame {
-//!     fn on_start(&mut self, game: &mut Game) {
-//!     }
-//!
 //!     fn on_event(&mut self, game: &mut Game, event: GameEvent) {
 //!         match event.into() {
 //!             SimpleEvent::Just(KeyCode::Char(ch)) => {
@@ -31,8 +48,6 @@
 //!         }
 //!
 //!     }
-//!
-//!     fn on_tick(&mut self, _game: &mut Game) {}
 //! }
 //!
 //! fn main() -> Result<(), Box<dyn Error>> {
@@ -53,47 +68,120 @@
 //! ```
 
 use crossterm::{
-    event::{self, poll, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use event_source::{AlongsideTerminal, ScriptedEvents};
+use frame_stats::FrameStats;
+use recording::ReplayEvents;
+use render_cache::{CachedView, RenderCache};
 use std::{
-    io,
+    fs::File,
+    io::{self, LineWriter},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
     time::{Duration, Instant},
 };
 use tui::{
-    backend::{Backend, CrosstermBackend},
-    layout::{Alignment, Constraint, Direction, Layout},
+    backend::{Backend, CrosstermBackend, TestBackend},
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout},
     style::{Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Paragraph, Widget, Wrap},
     Frame, Terminal,
 };
+use unicode_width::UnicodeWidthStr;
 
 mod charview;
+mod clock;
 mod controller;
+mod event_source;
+mod frame_stats;
 mod game;
 mod game_error;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod hud;
 mod message;
+mod recording;
+mod render_cache;
 mod styled_characters;
+mod theme;
+mod tween;
 
+pub use clock::{Clock, ManualClock, SystemClock};
 pub use controller::Controller;
+pub use event_source::{EventSource, TerminalEvents};
 pub use game::{
-    Color as GameColor, Game, GameEvent, GameStyle, KeyCode, KeyEvent, KeyEventKind, KeyEventState,
-    KeyModifiers, MouseEvent, SimpleEvent, StyledCharacter, ViewportLocation,
+    Color as GameColor, Font, Game, GameEvent, GameStyle, KeyCode, KeyEvent, KeyEventKind,
+    KeyEventState, KeyModifiers, MouseButton, MouseEvent, MouseEventKind, SimpleEvent,
+    StyledCharacter, ViewportLocation,
 };
 pub use game_error::GameError;
-pub use message::Message;
-pub use tui::style::Modifier as Font;
+#[cfg(feature = "gamepad")]
+pub use gamepad::{GamepadAxis, GamepadButton, GamepadEvent};
+pub use hud::Anchor;
+pub use message::{Message, MessagePosition};
+pub use theme::Theme;
+pub use tui::layout::{Alignment, Rect};
+pub use tui::widgets::Borders;
+pub use tween::{lerp, Easing, Tween};
 
-pub use charview::{chunkmap::ChunkMap, CharChunkMap, CharView};
+pub use charview::{
+    chunkmap::{ChunkMap, Entry as ChunkMapEntry, OccupiedEntry, VacantEntry},
+    minimap::Minimap,
+    CharChunkMap, CharView, SplitDirection,
+};
 
-/// The required screen height termgame can play at.
+/// The default screen height termgame plays at.
 /// Set to the size of a standard vt100
 pub const SCREEN_HEIGHT: u16 = 24;
-/// The required screen width termgame can play at.
+/// The default screen width termgame plays at.
 pub const SCREEN_WIDTH: u16 = 80;
 
+/// How long [`GameSettings::key_repeat`] keeps repeating a key which the
+/// terminal hasn't repeated, unless [`GameSettings::track_held_keys`] says
+/// otherwise.
+const KEY_REPEAT_TIMEOUT: Duration = Duration::from_millis(600);
+
+/// A closure deciding whether an event should quit the game. These are
+/// kept behind an [`Arc`] so that [`GameSettings`] is `Clone` and `Send`,
+/// as are [`SharedEventSource`] and [`SharedClock`].
+#[derive(Clone)]
+struct QuitPredicate(Arc<dyn Fn(&Event) -> bool + Send + Sync>);
+
+impl std::fmt::Debug for QuitPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("QuitPredicate")
+    }
+}
+
+/// An extra [`EventSource`] for the game.
+#[derive(Clone)]
+struct SharedEventSource(Arc<Mutex<dyn EventSource + Send>>);
+
+impl std::fmt::Debug for SharedEventSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("SharedEventSource")
+    }
+}
+
+/// The [`Clock`] used by the game.
+#[derive(Clone)]
+struct SharedClock(Arc<dyn Clock + Send + Sync>);
+
+impl std::fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("SharedClock")
+    }
+}
+
 /// This struct allows you to configure how [`run_game`] works.
 #[derive(Debug, Clone)]
 pub struct GameSettings {
@@ -102,9 +190,108 @@ pub struct GameSettings {
     /// lead to lag in your game.
     tick_duration: Duration,
 
-    /// This specifies what key combination will cause the game to end.
-    /// By default this is Ctrl-C
-    quit_event: Option<Event>,
+    /// If Some, this specifies how often the screen is redrawn, independently
+    /// of `tick_duration`. By default this is None, meaning the screen is
+    /// redrawn after every tick and every event.
+    render_duration: Option<Duration>,
+
+    /// This specifies what key combinations will cause the game to end.
+    /// By default this is only Ctrl-C
+    quit_events: Vec<Event>,
+
+    /// If Some, any event this returns `true` for will also cause
+    /// the game to end. By default this is None.
+    quit_when: Option<QuitPredicate>,
+
+    /// If Some, this event removes the current message (showing the next
+    /// queued message, if any) instead of being passed to the [`Controller`].
+    /// By default this is None.
+    dismiss_message_event: Option<Event>,
+
+    /// If Some, events from this source are given to the game as well as
+    /// events from the terminal. By default this is None.
+    event_source: Option<SharedEventSource>,
+
+    /// If Some, every event the game receives is written to this file.
+    /// By default this is None.
+    record_to: Option<PathBuf>,
+
+    /// If Some, the game measures time with this clock. By default this is
+    /// None, which means [`SystemClock`] is used by [`run_game`], and a
+    /// [`ManualClock`] is used by [`run_game_headless`].
+    clock: Option<SharedClock>,
+
+    /// If Some, the game ends after this many ticks. By default this is None.
+    max_ticks: Option<u64>,
+
+    /// If Some, the game ends once it has run for this long.
+    /// By default this is None.
+    time_limit: Option<Duration>,
+
+    /// This specifies the width of the screen the game is played on.
+    /// By default this is [`SCREEN_WIDTH`].
+    screen_width: u16,
+
+    /// This specifies the height of the screen the game is played on.
+    /// By default this is [`SCREEN_HEIGHT`].
+    screen_height: u16,
+
+    /// If Some, this is shown instead of the game when the terminal is
+    /// too small. By default this is None, which shows the size needed.
+    too_small_message: Option<String>,
+
+    /// This specifies whether as much of the game as fits is drawn when
+    /// the terminal is too small. By default this is `false`.
+    draw_when_too_small: bool,
+
+    /// This specifies which borders are drawn around the game area.
+    /// By default, all of them are.
+    game_border: Borders,
+
+    /// If Some, the name of the game, used in the messages termgame shows.
+    /// By default this is None.
+    name: Option<String>,
+
+    /// If Some, this is shown as a title on the game area's border.
+    /// By default this is None.
+    game_title: Option<String>,
+
+    /// If Some, the style of the border around the game area.
+    /// By default this is None.
+    game_border_style: Option<GameStyle>,
+
+    /// If Some, the space around the game (when the terminal is larger
+    /// than the screen) is filled with this color. By default this is None.
+    margin_color: Option<GameColor>,
+
+    /// This specifies whether mouse events are captured by the game.
+    /// By default this is `true`.
+    mouse_capture: bool,
+
+    /// This specifies whether the game is drawn on the terminal's
+    /// alternate screen. By default this is `true`.
+    alternate_screen: bool,
+
+    /// This specifies whether the frame rate and average tick time are
+    /// shown in the top-right corner of the screen. By default this is `false`.
+    show_fps: bool,
+
+    /// If Some, a faint `+` is drawn at every game coordinate whose `x`
+    /// and `y` are both multiples of this. By default this is None.
+    debug_grid: Option<u32>,
+
+    /// If Some, the game keeps track of which keys are held down, and
+    /// a key counts as held for this long after it was last pressed
+    /// (unless the terminal says it was released). By default this is None.
+    held_key_timeout: Option<Duration>,
+
+    /// If Some, the last key pressed is given to the game again this often,
+    /// until another event happens. By default this is None.
+    key_repeat: Option<Duration>,
+
+    /// This specifies whether the terminal is asked to report key releases
+    /// and repeats (on terminals which support it). By default this is `false`.
+    keyboard_enhancement: bool,
 }
 
 impl GameSettings {
@@ -119,9 +306,307 @@ impl GameSettings {
         self
     }
 
+    /// Set how often the screen is redrawn, independently of how often
+    /// [`Controller::on_tick`] is called. If `None`, the screen is redrawn
+    /// after every tick and every event.
+    pub fn render_duration(mut self, render_duration: Option<Duration>) -> GameSettings {
+        self.render_duration = render_duration;
+        self
+    }
+
     /// Set a new key combination to quit the game (or disable it entirely).
+    /// This replaces any key combinations set by [`GameSettings::quit_events`].
     pub fn quit_event(mut self, quit_event: Option<Event>) -> GameSettings {
-        self.quit_event = quit_event;
+        self.quit_events = quit_event.into_iter().collect();
+        self
+    }
+
+    /// Set a list of key combinations, any of which will quit the game.
+    /// If the list is empty, there is no way to quit the game by a key
+    /// combination.
+    ///
+    /// ```rust
+    /// use termgame::{GameSettings, KeyCode, SimpleEvent};
+    /// GameSettings::new().quit_events(vec![
+    ///     SimpleEvent::WithControl(KeyCode::Char('c')).into(),
+    ///     SimpleEvent::Just(KeyCode::Esc).into(),
+    /// ]);
+    /// ```
+    pub fn quit_events(mut self, quit_events: Vec<Event>) -> GameSettings {
+        self.quit_events = quit_events;
+        self
+    }
+
+    /// Set a function which decides whether an event should quit the game.
+    /// If any quit events are also set, the game will end when either the
+    /// event matches one of them, or this function returns `true`.
+    ///
+    /// ```rust
+    /// use termgame::{GameSettings, KeyCode, SimpleEvent};
+    /// GameSettings::new().quit_when(Box::new(|event| {
+    ///     matches!(SimpleEvent::from(event.clone()), SimpleEvent::Just(KeyCode::Esc))
+    /// }));
+    /// ```
+    pub fn quit_when(
+        mut self,
+        quit_when: Box<dyn Fn(&Event) -> bool + Send + Sync>,
+    ) -> GameSettings {
+        self.quit_when = Some(QuitPredicate(Arc::from(quit_when)));
+        self
+    }
+
+    /// Set an event which removes the current message, showing the
+    /// next message added with [`Game::push_message`] if there is one.
+    /// While a message is showing, this event is not passed to
+    /// [`Controller::on_event`].
+    pub fn dismiss_message_event(mut self, dismiss_message_event: Option<Event>) -> GameSettings {
+        self.dismiss_message_event = dismiss_message_event;
+        self
+    }
+
+    /// Give the game events from `event_source`, as well as from the
+    /// terminal. This is useful for demos that play themselves, or for
+    /// controlling the game from another thread; a
+    /// [`Receiver`](std::sync::mpsc::Receiver) is an [`EventSource`].
+    ///
+    /// Events from this source go through the game loop exactly like
+    /// events from the terminal, so they can also quit the game.
+    ///
+    /// ```rust
+    /// use std::sync::mpsc;
+    /// use termgame::{GameSettings, KeyCode, SimpleEvent};
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let settings = GameSettings::new().event_source(receiver);
+    /// sender.send(SimpleEvent::Just(KeyCode::Right).into()).unwrap();
+    /// ```
+    pub fn event_source(mut self, event_source: impl EventSource + Send + 'static) -> GameSettings {
+        self.event_source = Some(SharedEventSource(Arc::new(Mutex::new(event_source))));
+        self
+    }
+
+    /// Write every event the game receives to the file at `path`, along
+    /// with when it happened, so that the game can be played again with
+    /// [`GameSettings::replay_from`]. This is handy for reproducing bugs.
+    ///
+    /// The file is overwritten when the game starts. Each line of it is the
+    /// number of milliseconds since the game started, followed by the event.
+    pub fn record_to(mut self, path: impl Into<PathBuf>) -> GameSettings {
+        self.record_to = Some(path.into());
+        self
+    }
+
+    /// Replay the events recorded in the file at `path` by
+    /// [`GameSettings::record_to`], at the same times they originally
+    /// happened. Events from the terminal are still received as well.
+    ///
+    /// This replaces any [`GameSettings::event_source`]. If the file
+    /// can't be read, [`run_game`] returns [`GameError::ReadingEvents`].
+    pub fn replay_from(self, path: impl Into<PathBuf>) -> GameSettings {
+        self.event_source(ReplayEvents::new(path.into()))
+    }
+
+    /// Measure time with `clock`, instead of the system's clock. This
+    /// decides when ticks happen, and what [`Game::delta_time`] is.
+    ///
+    /// Giving the game a [`ManualClock`] means time only passes when you
+    /// say so (or when the game is waiting for events), so tests can run
+    /// an exact number of ticks:
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use termgame::{run_game_headless, CharChunkMap, Controller, GameSettings, ManualClock};
+    ///
+    /// struct MyGame;
+    /// impl Controller for MyGame {}
+    ///
+    /// let clock = ManualClock::new();
+    /// let start = termgame::Clock::now(&clock);
+    /// run_game_headless(
+    ///     &mut MyGame,
+    ///     GameSettings::new()
+    ///         .tick_duration(Duration::from_millis(100))
+    ///         .clock(clock.clone()),
+    ///     80,
+    ///     24,
+    ///     &mut CharChunkMap::new(),
+    ///     vec![],
+    ///     3,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(termgame::Clock::now(&clock) - start, Duration::from_millis(300));
+    /// ```
+    pub fn clock(mut self, clock: impl Clock + Send + Sync + 'static) -> GameSettings {
+        self.clock = Some(SharedClock(Arc::new(clock)));
+        self
+    }
+
+    /// End the game once `max_ticks` ticks have happened, as if
+    /// [`Game::end_game`] had been called. [`Controller::on_end`] is
+    /// still called.
+    ///
+    /// Ticks count even while [`Controller::on_tick`] isn't being called
+    /// (because the game is paused, or showing a modal message), so that a
+    /// game which stays paused still ends.
+    ///
+    /// [`run_game_headless`] ends after whichever of this and its own
+    /// `ticks` comes first.
+    pub fn max_ticks(mut self, max_ticks: Option<u64>) -> GameSettings {
+        self.max_ticks = max_ticks;
+        self
+    }
+
+    /// End the game once it has run for `time_limit` (measured with the
+    /// game's [`GameSettings::clock`]), as if [`Game::end_game`] had been
+    /// called. [`Controller::on_end`] is still called.
+    pub fn time_limit(mut self, time_limit: Option<Duration>) -> GameSettings {
+        self.time_limit = time_limit;
+        self
+    }
+
+    /// Set the width of the screen. Terminals narrower than this
+    /// will be asked to resize.
+    pub fn screen_width(mut self, screen_width: u16) -> GameSettings {
+        self.screen_width = screen_width;
+        self
+    }
+
+    /// Set the height of the screen. Terminals shorter than this
+    /// will be asked to resize.
+    pub fn screen_height(mut self, screen_height: u16) -> GameSettings {
+        self.screen_height = screen_height;
+        self
+    }
+
+    /// Set the message shown instead of the game when the terminal is
+    /// smaller than the screen (see [`GameSettings::screen_width`] and
+    /// [`GameSettings::screen_height`]). If `None`, the message says what
+    /// size the terminal needs to be.
+    pub fn too_small_message(mut self, too_small_message: Option<String>) -> GameSettings {
+        self.too_small_message = too_small_message;
+        self
+    }
+
+    /// Set whether the game is still drawn when the terminal is smaller
+    /// than the screen. If `true`, as much of the game as fits is drawn,
+    /// and the rest is cut off; the game still behaves as if the screen
+    /// was its full size. If `false` (the default), a message asking for a
+    /// larger terminal is shown instead (see
+    /// [`GameSettings::too_small_message`]).
+    pub fn draw_when_too_small(mut self, draw_when_too_small: bool) -> GameSettings {
+        self.draw_when_too_small = draw_when_too_small;
+        self
+    }
+
+    /// Set which borders are drawn around the game area. If a border
+    /// is removed, the game area grows to use the space it took up.
+    ///
+    /// ```rust
+    /// use termgame::{Borders, GameSettings};
+    /// GameSettings::new().game_border(Borders::NONE);
+    /// ```
+    pub fn game_border(mut self, game_border: Borders) -> GameSettings {
+        self.game_border = game_border;
+        self
+    }
+
+    /// Set the name of the game. This is used wherever termgame talks
+    /// about the game, like the message shown when the terminal is too
+    /// small, and as the title of messages which don't have their own.
+    ///
+    /// ```rust
+    /// use termgame::GameSettings;
+    /// GameSettings::new().name(String::from("Rusty Dungeon"));
+    /// ```
+    pub fn name(mut self, name: String) -> GameSettings {
+        self.name = Some(name);
+        self
+    }
+
+    /// Set a title to show on the game area's border (or remove it).
+    /// This can be changed during the game with [`Game::set_title`].
+    pub fn game_title(mut self, game_title: Option<String>) -> GameSettings {
+        self.game_title = game_title;
+        self
+    }
+
+    /// Set the colours and font of the border (and title) around the game
+    /// area. This can be changed during the game with
+    /// [`Game::set_border_style`].
+    ///
+    /// ```rust
+    /// use termgame::{GameColor, GameSettings, GameStyle};
+    /// GameSettings::new().game_border_style(GameStyle::new().color(Some(GameColor::Yellow)));
+    /// ```
+    pub fn game_border_style(mut self, style: GameStyle) -> GameSettings {
+        self.game_border_style = Some(style);
+        self
+    }
+
+    /// Set the color of the space around the game, when the terminal
+    /// is larger than the screen. If `None`, the terminal's own background
+    /// is shown.
+    pub fn margin_color(mut self, margin_color: Option<GameColor>) -> GameSettings {
+        self.margin_color = margin_color;
+        self
+    }
+
+    /// Set whether the game captures mouse events. If this is `false`,
+    /// no mouse events will be received, but the terminal's own text
+    /// selection will keep working while the game runs.
+    pub fn mouse_capture(mut self, mouse_capture: bool) -> GameSettings {
+        self.mouse_capture = mouse_capture;
+        self
+    }
+
+    /// Set whether the game is drawn on the terminal's alternate screen.
+    /// If this is `false`, the last frame of the game (and anything printed
+    /// while it ran) will stay in the terminal after the game ends.
+    pub fn alternate_screen(mut self, alternate_screen: bool) -> GameSettings {
+        self.alternate_screen = alternate_screen;
+        self
+    }
+
+    /// Set whether the number of frames drawn per second, and the average
+    /// time [`Controller::on_tick`] takes, are shown in the top-right corner
+    /// of the screen. This is useful for checking whether your game can keep
+    /// up with its tick duration.
+    pub fn show_fps(mut self, show_fps: bool) -> GameSettings {
+        self.show_fps = show_fps;
+        self
+    }
+
+    /// Set whether a faint grid is drawn over the game, to help see where
+    /// coordinates are on the screen. If this is `Some(spacing)`, a `+` is
+    /// drawn in every empty cell whose `x` and `y` are both multiples of
+    /// `spacing` (so `(0, 0)` always has one). The grid is only drawn on
+    /// the screen, and is never added to the game's [`CharChunkMap`]. It
+    /// isn't drawn in half-block mode (see [`Game::set_half_blocks`]).
+    ///
+    /// A spacing of `Some(0)` is treated like `None`.
+    pub fn debug_grid(mut self, spacing: Option<u32>) -> GameSettings {
+        self.debug_grid = spacing.filter(|&spacing| spacing > 0);
+        self
+    }
+
+    /// Track held keys for [`Game::keys_down`]. A key counts as held for
+    /// `held_key_timeout` after it was last pressed; see [held keys](crate#held-keys).
+    pub fn track_held_keys(mut self, held_key_timeout: Option<Duration>) -> GameSettings {
+        self.held_key_timeout = held_key_timeout;
+        self
+    }
+
+    /// Give the last key press to [`Controller::on_event`] again every
+    /// `interval`, until another event happens; see [held keys](crate#held-keys).
+    pub fn key_repeat(mut self, interval: Option<Duration>) -> GameSettings {
+        self.key_repeat = interval;
+        self
+    }
+
+    /// Set whether to ask the terminal to report key releases and repeats;
+    /// see [held keys](crate#held-keys).
+    pub fn keyboard_enhancement(mut self, keyboard_enhancement: bool) -> GameSettings {
+        self.keyboard_enhancement = keyboard_enhancement;
         self
     }
 }
@@ -130,107 +615,627 @@ impl Default for GameSettings {
     fn default() -> GameSettings {
         GameSettings {
             tick_duration: Duration::from_millis(50),
-            quit_event: Some(SimpleEvent::WithControl(KeyCode::Char('c')).into()),
+            render_duration: None,
+            quit_events: vec![SimpleEvent::WithControl(KeyCode::Char('c')).into()],
+            quit_when: None,
+            dismiss_message_event: None,
+            event_source: None,
+            record_to: None,
+            clock: None,
+            max_ticks: None,
+            time_limit: None,
+            screen_width: SCREEN_WIDTH,
+            screen_height: SCREEN_HEIGHT,
+            too_small_message: None,
+            draw_when_too_small: false,
+            game_border: Borders::ALL,
+            name: None,
+            game_title: None,
+            game_border_style: None,
+            margin_color: None,
+            mouse_capture: true,
+            alternate_screen: true,
+            show_fps: false,
+            debug_grid: None,
+            held_key_timeout: None,
+            key_repeat: None,
+            keyboard_enhancement: false,
         }
     }
 }
 
 /// Starts a game with a given [`Controller`], which refreshes at the given tick_duration (a [`Duration`]).
+///
+/// The terminal is always restored once the game ends, even if one of the
+/// [`Controller`]'s event-handlers panics (in which case the panic continues
+/// once the terminal has been restored).
 pub fn run_game(controller: &mut dyn Controller, settings: GameSettings) -> Result<(), GameError> {
+    run_game_with_map(controller, settings, &mut ChunkMap::new())
+}
+
+/// Starts a game like [`run_game`], but draws the game's characters in a
+/// [`CharChunkMap`] you provide. Since you still own the map once the game
+/// ends, you can look at what was on the screen (for example, to report a
+/// final score from `main`).
+///
+/// ```rust,no_run
+/// use termgame::{run_game_with_map, CharChunkMap, Controller, GameSettings};
+///
+/// struct MyGame;
+/// impl Controller for MyGame {}
+///
+/// let mut chunks = CharChunkMap::new();
+/// run_game_with_map(&mut MyGame, GameSettings::new(), &mut chunks).unwrap();
+/// println!("The top-left character was {:?}", chunks.get(0, 0));
+/// ```
+pub fn run_game_with_map(
+    controller: &mut dyn Controller,
+    settings: GameSettings,
+    chunks: &mut CharChunkMap,
+) -> Result<(), GameError> {
     // setup terminal
     enable_raw_mode().map_err(|e| GameError::RawMode(Box::new(e)))?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
-        .map_err(GameError::TerminalExecute)?;
+    if settings.alternate_screen {
+        execute!(stdout, EnterAlternateScreen).map_err(GameError::TerminalExecute)?;
+    }
+    if settings.mouse_capture {
+        execute!(stdout, EnableMouseCapture).map_err(GameError::TerminalExecute)?;
+    }
+    if settings.keyboard_enhancement {
+        // This fails on terminals which can't support it (like the legacy
+        // Windows console); the game just goes on without it.
+        let _ = execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                    | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES
+            )
+        );
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend).map_err(GameError::TerminalMode)?;
 
     // create app and run it
-    let res = run_gameloop(&mut terminal, controller, settings);
+    run_and_restore(&mut terminal, controller, settings, chunks)
+}
 
-    // restore terminal
-    disable_raw_mode().map_err(|e| GameError::RawMode(Box::new(e)))?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
+/// Runs a game without setting up a real terminal, which lets you test a
+/// [`Controller`] (for example, in `cargo test`).
+///
+/// The game is drawn to a screen of `width` columns and `height` rows,
+/// which is returned once the game is over, and characters are placed in
+/// the given [`CharChunkMap`], so you can look at both. Every event in
+/// `events` is given to the game straight away, one after another; after
+/// that, the game runs for `ticks` ticks and then ends. As with
+/// [`GameSettings::max_ticks`], ticks count even while the game is paused.
+/// Unless the settings give the game a different [`GameSettings::clock`],
+/// time is measured with a [`ManualClock`], so no real time passes and the
+/// game runs as fast as it can.
+///
+/// ```rust
+/// use termgame::{
+///     run_game_headless, CharChunkMap, Controller, Game, GameEvent, GameSettings, KeyCode,
+///     SimpleEvent, StyledCharacter,
+/// };
+///
+/// struct MyGame;
+///
+/// impl Controller for MyGame {
+///     fn on_event(&mut self, game: &mut Game, event: GameEvent) {
+///         if let SimpleEvent::Just(KeyCode::Char(ch)) = event.into() {
+///             game.set_screen_char(1, 1, Some(StyledCharacter::new(ch)));
+///         }
+///     }
+/// }
+///
+/// let mut chunks = CharChunkMap::new();
+/// let screen = run_game_headless(
+///     &mut MyGame,
+///     GameSettings::new(),
+///     80,
+///     24,
+///     &mut chunks,
+///     vec![SimpleEvent::Just(KeyCode::Char('x')).into()],
+///     10,
+/// )
+/// .unwrap();
+/// assert_eq!(chunks.get(1, 1).map(|c| c.c), Some('x'));
+/// assert_eq!(screen.get(2, 2).symbol, "x");
+/// ```
+pub fn run_game_headless(
+    controller: &mut dyn Controller,
+    settings: GameSettings,
+    width: u16,
+    height: u16,
+    chunks: &mut CharChunkMap,
+    events: Vec<Event>,
+    ticks: u64,
+) -> Result<Buffer, GameError> {
+    let mut terminal =
+        Terminal::new(TestBackend::new(width, height)).expect("a TestBackend can't fail");
+    run_game_with_backend(controller, settings, &mut terminal, chunks, events, ticks)?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// Runs a game like [`run_game_headless`], but on an already configured
+/// terminal.
+fn run_game_with_backend<B: Backend>(
+    controller: &mut dyn Controller,
+    settings: GameSettings,
+    terminal: &mut Terminal<B>,
+    chunks: &mut CharChunkMap,
+    events: Vec<Event>,
+    ticks: u64,
+) -> Result<(), GameError> {
+    let clock = match &settings.clock {
+        Some(SharedClock(clock)) => clock.clone(),
+        None => Arc::new(ManualClock::new()),
+    };
+    run_gameloop(
+        terminal,
+        controller,
+        &settings,
+        chunks,
+        &mut ScriptedEvents::new(events),
+        &*clock,
+        Some(ticks),
     )
-    .map_err(GameError::TerminalExecute)?;
-    terminal.show_cursor().map_err(GameError::TerminalMode)?;
+}
 
-    res.map_err(GameError::Running)
+/// Runs the event-loop on an already configured terminal, then restores
+/// the terminal. If the event-loop panics, the terminal is restored before
+/// the panic is resumed.
+fn run_and_restore<B: Backend + io::Write>(
+    terminal: &mut Terminal<B>,
+    controller: &mut dyn Controller,
+    settings: GameSettings,
+    chunks: &mut CharChunkMap,
+) -> Result<(), GameError> {
+    let clock: &dyn Clock = match &settings.clock {
+        Some(SharedClock(clock)) => &**clock,
+        None => &SystemClock,
+    };
+    let res = panic::catch_unwind(AssertUnwindSafe(|| match &settings.event_source {
+        Some(SharedEventSource(event_source)) => run_gameloop(
+            terminal,
+            controller,
+            &settings,
+            chunks,
+            &mut AlongsideTerminal(
+                &mut *event_source.lock().unwrap_or_else(PoisonError::into_inner),
+            ),
+            clock,
+            None,
+        ),
+        None => run_gameloop(
+            terminal,
+            controller,
+            &settings,
+            chunks,
+            &mut TerminalEvents,
+            clock,
+            None,
+        ),
+    }));
+
+    let restored = restore_terminal(terminal, &settings);
+    let res = res.unwrap_or_else(|e| panic::resume_unwind(e));
+    restored?;
+
+    res
+}
+
+/// Undoes the terminal configuration done by [`run_game`].
+fn restore_terminal<B: Backend + io::Write>(
+    terminal: &mut Terminal<B>,
+    settings: &GameSettings,
+) -> Result<(), GameError> {
+    disable_raw_mode().map_err(|e| GameError::RawMode(Box::new(e)))?;
+    if settings.keyboard_enhancement {
+        let _ = execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags);
+    }
+    if settings.alternate_screen {
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)
+            .map_err(GameError::TerminalExecute)?;
+    }
+    if settings.mouse_capture {
+        execute!(terminal.backend_mut(), DisableMouseCapture)
+            .map_err(GameError::TerminalExecute)?;
+    }
+    terminal.show_cursor().map_err(GameError::TerminalMode)?;
+    Ok(())
 }
 
 /// Function is called internally once the terminal is configured,
 /// and contains the event-loop.
 ///
+/// Events are read from `events`, and time is measured using `clock`.
+/// If `max_ticks` (or [`GameSettings::max_ticks`]) is Some, the game ends
+/// once that many ticks have happened, including ticks skipped because the
+/// game was paused.
+///
 /// This function does not clean up the terminal after itself,
 /// it assumes that another function ([`run_game`]) will do that.
 fn run_gameloop<B: Backend>(
     terminal: &mut Terminal<B>,
     controller: &mut dyn Controller,
-    settings: GameSettings,
-) -> io::Result<()> {
-    let mut chunks: CharChunkMap = ChunkMap::new();
-    let mut last_tick = Instant::now();
-    let mut game = Game::new(&mut chunks);
+    settings: &GameSettings,
+    chunks: &mut CharChunkMap,
+    events: &mut dyn EventSource,
+    clock: &dyn Clock,
+    max_ticks: Option<u64>,
+) -> Result<(), GameError> {
+    let started = clock.now();
+    let max_ticks = match (max_ticks, settings.max_ticks) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+    let mut last_tick = started;
+    let mut last_render: Option<Instant> = None;
+    let mut ticks: u64 = 0;
+    let mut frame_stats = FrameStats::default();
+    let mut render_cache = RenderCache::default();
+    // The key press being repeated, when it is next repeated, and when it
+    // stops being repeated unless the terminal repeats it first.
+    let mut repeating: Option<(Event, Instant, Instant)> = None;
+    let key_repeat_timeout = settings.held_key_timeout.unwrap_or(KEY_REPEAT_TIMEOUT);
+    let mut recording = match &settings.record_to {
+        Some(path) => Some(LineWriter::new(
+            File::create(path).map_err(GameError::Recording)?,
+        )),
+        None => None,
+    };
+    #[cfg(feature = "gamepad")]
+    let mut gamepads = gamepad::Gamepads::new();
+    let mut game = Game::new(chunks);
+    game.now = last_tick;
+    game.screen_dimensions = (settings.screen_width, settings.screen_height);
+    game.game_border = settings.game_border;
+    game.game_title = settings.game_title.clone();
+    game.border_style = settings.game_border_style.clone();
+    game.held_key_timeout = settings.held_key_timeout;
     controller.on_start(&mut game);
     loop {
-        {
-            terminal.draw(|f| ui(f, &game))?;
+        game.now = clock.now();
+        game.expire_message();
+        game.update_viewport_scroll();
+        let render_due = game.redraw_requested
+            || match (settings.render_duration, last_render) {
+                (Some(render_duration), Some(last_render)) => {
+                    game.now.duration_since(last_render) >= render_duration
+                }
+                _ => true,
+            };
+        if render_due {
+            controller.on_render(&mut game);
+            let mut view_area = None;
+            terminal
+                .draw(|f| view_area = ui(f, &game, settings, &frame_stats, &mut render_cache))
+                .map_err(GameError::Drawing)?;
+            game.view_area = view_area;
+            game.redraw_requested = false;
+            game.dirty_rows.clear();
+            game.all_dirty = false;
+            last_render = Some(game.now);
+            frame_stats.record_frame(game.now);
         }
-        let timeout = settings
+        let mut timeout = settings
             .tick_duration
-            .checked_sub(last_tick.elapsed())
-            .unwrap_or_else(|| Duration::from_secs(0));
-        if poll(timeout)? {
-            let event = event::read()?;
-            if let Some(quit_event) = settings.quit_event.as_ref() {
-                if &event == quit_event {
-                    return Ok(());
+            .saturating_sub(game.now.duration_since(last_tick));
+        if let (Some(render_duration), Some(last_render)) = (settings.render_duration, last_render)
+        {
+            timeout =
+                timeout.min(render_duration.saturating_sub(game.now.duration_since(last_render)));
+        }
+        if let Some(time_limit) = settings.time_limit {
+            timeout = timeout.min(time_limit.saturating_sub(game.now.duration_since(started)));
+        }
+        if let Some((_, next_repeat, _)) = &repeating {
+            timeout = timeout.min(next_repeat.saturating_duration_since(game.now));
+        }
+        let event = events
+            .next_event(timeout)
+            .map_err(GameError::ReadingEvents)?;
+        if event.is_none() {
+            clock.waited(timeout);
+            let now = clock.now();
+            if repeating
+                .as_ref()
+                .is_some_and(|(_, _, stop_at)| now >= *stop_at)
+            {
+                repeating = None;
+            }
+            if let (Some((event, next_repeat, _)), Some(interval)) =
+                (&mut repeating, settings.key_repeat)
+            {
+                if now >= *next_repeat && !game.is_showing_modal() && !game.is_prompting() {
+                    game.now = now;
+                    *next_repeat = now + interval;
+                    controller.on_event(&mut game, event.clone());
                 }
             }
-            controller.on_event(&mut game, event);
         }
-        if game.game_will_end() {
-            return Ok(());
+        if let Some(event) = event {
+            repeating = None;
+            game.now = clock.now();
+            if let Some(recording) = &mut recording {
+                recording::write_event(recording, game.now.duration_since(started), &event)
+                    .map_err(GameError::Recording)?;
+            }
+            if settings.quit_events.contains(&event)
+                || settings
+                    .quit_when
+                    .as_ref()
+                    .is_some_and(|QuitPredicate(quit_when)| quit_when(&event))
+            {
+                break;
+            }
+            game.track_held_keys(&event);
+            let is_key_press = matches!(
+                event,
+                Event::Key(KeyEvent {
+                    kind: KeyEventKind::Press,
+                    ..
+                })
+            );
+            if game.is_showing_modal() && is_key_press {
+                game.set_message(None);
+                controller.on_modal_closed(&mut game);
+            } else if let (true, Event::Key(key)) = (game.is_prompting(), &event) {
+                if key.kind != KeyEventKind::Release {
+                    if let Some(text) = game.type_into_prompt(*key) {
+                        controller.on_input_complete(&mut game, text);
+                    }
+                }
+            } else if game.get_message().is_some()
+                && settings.dismiss_message_event.as_ref() == Some(&event)
+            {
+                game.set_message(None);
+            } else {
+                if let Event::Resize(width, height) = event {
+                    controller.on_resize(&mut game, width, height);
+                }
+                if let (Some(interval), true) = (settings.key_repeat, is_key_press) {
+                    repeating = Some((
+                        event.clone(),
+                        game.now + interval,
+                        game.now + key_repeat_timeout,
+                    ));
+                }
+                controller.on_event(&mut game, event);
+            }
+        }
+        // Gamepads are checked whenever the terminal has been waited on,
+        // and are treated like the terminal's keys.
+        #[cfg(feature = "gamepad")]
+        while let Some(event) = gamepads.next_event() {
+            game.now = clock.now();
+            if game.is_showing_modal() {
+                if let GamepadEvent::Pressed(..) = event {
+                    game.set_message(None);
+                    controller.on_modal_closed(&mut game);
+                }
+            } else if !game.is_prompting() {
+                controller.on_gamepad_event(&mut game, event);
+            }
+        }
+        if game.game_will_end()
+            || settings
+                .time_limit
+                .is_some_and(|time_limit| clock.now().duration_since(started) >= time_limit)
+        {
+            break;
         }
 
-        if last_tick.elapsed() >= settings.tick_duration {
-            controller.on_tick(&mut game);
-            last_tick = Instant::now();
+        let now = clock.now();
+        if now.duration_since(last_tick) >= settings.tick_duration {
+            if max_ticks.is_some_and(|max_ticks| ticks >= max_ticks) {
+                break;
+            }
+            if !game.is_paused() && !game.is_showing_modal() {
+                game.now = now;
+                game.delta_time = now.duration_since(last_tick);
+                game.tick_flashes();
+                controller.on_tick(&mut game);
+                frame_stats.record_tick(clock.now().duration_since(now));
+            }
+            last_tick = now;
+            ticks += 1;
 
-            if game.game_will_end() {
-                return Ok(());
+            if game.game_will_end() || max_ticks.is_some_and(|max_ticks| ticks >= max_ticks) {
+                break;
             }
         }
     }
+    controller.on_end(&mut game);
+    // Draw the final state of the game, so it's what is left behind.
+    controller.on_render(&mut game);
+    terminal
+        .draw(|f| {
+            ui(f, &game, settings, &frame_stats, &mut render_cache);
+        })
+        .map_err(GameError::Drawing)?;
+    Ok(())
+}
+
+/// Draw one frame of `game`, as it would be shown on a `width` by
+/// `height` terminal, and return the characters on the screen (without
+/// any colours or styles) as rows separated by newlines. This is useful
+/// for snapshot tests, which check that a game looks the way it should.
+///
+/// The frame is drawn with the default [`GameSettings`], so things like
+/// [`GameSettings::show_fps`] are not shown.
+///
+/// ```rust
+/// use termgame::{render_to_string, CharChunkMap, Game, StyledCharacter};
+/// let mut chunks = CharChunkMap::new();
+/// let mut game = Game::new(&mut chunks);
+/// game.set_screen_char(0, 0, Some(StyledCharacter::new('@')));
+/// let screen = render_to_string(&game, 80, 24);
+/// assert_eq!(screen.lines().count(), 24);
+/// assert!(screen.lines().nth(1).unwrap().starts_with("│@ "));
+/// ```
+pub fn render_to_string(game: &Game, width: u16, height: u16) -> String {
+    let mut terminal =
+        Terminal::new(TestBackend::new(width, height)).expect("a TestBackend can't fail");
+    let settings = GameSettings::new();
+    terminal
+        .draw(|f| {
+            ui(
+                f,
+                game,
+                &settings,
+                &FrameStats::default(),
+                &mut RenderCache::default(),
+            );
+        })
+        .expect("a TestBackend can't fail");
+
+    let buffer = terminal.backend().buffer();
+    let mut rows = Vec::new();
+    for y in buffer.area.top()..buffer.area.bottom() {
+        let mut row = String::new();
+        let mut x = buffer.area.left();
+        while x < buffer.area.right() {
+            let symbol = &buffer.get(x, y).symbol;
+            row.push_str(symbol);
+            // The cell after a wide character is covered by it.
+            x += u16::try_from(symbol.width()).unwrap_or(1).max(1);
+        }
+        rows.push(row);
+    }
+    rows.join("\n")
 }
 
-/// Creates a block for the [`ui`] function, with the given title.
-fn create_block(title: Option<String>) -> tui::widgets::Block<'static> {
+/// The part of `area` which is inside `within`, or `None` if they don't
+/// overlap. Unlike [`Rect::intersection`], this can't overflow when
+/// `area` is far outside `within`.
+pub(crate) fn clip(area: Rect, within: Rect) -> Option<Rect> {
+    let left = area.x.max(within.x);
+    let top = area.y.max(within.y);
+    let right = area
+        .x
+        .saturating_add(area.width)
+        .min(within.x.saturating_add(within.width));
+    let bottom = area
+        .y
+        .saturating_add(area.height)
+        .min(within.y.saturating_add(within.height));
+    (left < right && top < bottom).then(|| Rect::new(left, top, right - left, bottom - top))
+}
+
+/// Creates a block for the [`ui`] function, with the given title and style.
+/// If there is no title, the game's name (see [`GameSettings::name`]) is
+/// used instead.
+fn create_block(
+    title: Option<String>,
+    name: Option<String>,
+    style: Style,
+) -> tui::widgets::Block<'static> {
     Block::default()
         .borders(Borders::ALL)
-        .style(Style::default().bg(GameColor::White).fg(GameColor::Black))
+        .style(style)
         .title(Span::styled(
-            title.unwrap_or_else(|| "Message".to_string()),
+            title.or(name).unwrap_or_else(|| "Message".to_string()),
             Style::default().add_modifier(Modifier::BOLD),
         ))
 }
 
+/// A widget which draws a `+` in the empty cells of the game area whose
+/// coordinates are multiples of `spacing`; see [`GameSettings::debug_grid`].
+struct DebugGrid {
+    viewport: ViewportLocation,
+    spacing: u32,
+}
+
+impl Widget for DebugGrid {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let spacing = i64::from(self.spacing);
+        let on_grid = |coord: i64| coord.rem_euclid(spacing) == 0;
+        for y in area.top()..area.bottom() {
+            if !on_grid(i64::from(self.viewport.y) + i64::from(y - area.y)) {
+                continue;
+            }
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                if on_grid(i64::from(self.viewport.x) + i64::from(x - area.x)) && cell.symbol == " "
+                {
+                    cell.set_char('+').set_fg(GameColor::DarkGray);
+                }
+            }
+        }
+    }
+}
+
+/// A widget which writes `text` starting at `(x, y)`, leaving out whatever
+/// is outside the area it is drawn in; see [`Game::draw_hud_text`].
+struct HudText<'a> {
+    x: i64,
+    y: i64,
+    text: &'a str,
+}
+
+impl Widget for HudText<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !(i64::from(area.top())..i64::from(area.bottom())).contains(&self.y) {
+            return;
+        }
+        for (x, c) in (self.x..).zip(self.text.chars()) {
+            if (i64::from(area.left())..i64::from(area.right())).contains(&x) {
+                buf.get_mut(x as u16, self.y as u16).set_char(c);
+            }
+        }
+    }
+}
+
+/// A widget which draws styles on top of single cells, given as
+/// `(column, row, style)`; see [`Game::flash`].
+struct Flashes(Vec<(u16, u16, Style)>);
+
+impl Widget for Flashes {
+    fn render(self, _area: Rect, buf: &mut Buffer) {
+        for (col, row, style) in self.0 {
+            buf.get_mut(col, row).set_style(style);
+        }
+    }
+}
+
 /// Creates the UI for a particular level.
-fn ui<B: Backend>(f: &mut Frame<B>, game: &Game) {
-    if f.size().height < SCREEN_HEIGHT || f.size().width < SCREEN_WIDTH {
+///
+/// Returns the area of the terminal that the game's characters were
+/// drawn in, or `None` if the game could not be shown.
+fn ui<B: Backend>(
+    f: &mut Frame<B>,
+    game: &Game,
+    settings: &GameSettings,
+    frame_stats: &FrameStats,
+    render_cache: &mut RenderCache,
+) -> Option<Rect> {
+    let (screen_width, screen_height) = game.screen_dimensions;
+    let too_small = f.size().height < screen_height || f.size().width < screen_width;
+    if too_small && !settings.draw_when_too_small {
+        let message = settings.too_small_message.clone().unwrap_or_else(|| {
+            let name = settings.name.as_deref().unwrap_or("This game");
+            format!("{name} needs a terminal of at least {screen_width} columns × {screen_height} rows!")
+        });
         let text = vec![Spans::from(Span::styled(
-            format!("cs6991's Explorer requires a {SCREEN_HEIGHT}x{SCREEN_WIDTH} terminal!"),
+            message,
             Style::default().fg(GameColor::Red),
         ))];
+        let title = match &settings.name {
+            Some(name) => format!("{name}: Error"),
+            None => String::from("Error"),
+        };
         let paragraph = Paragraph::new(text)
-            .block(Block::default().title("Error").borders(Borders::ALL))
+            .block(Block::default().title(title).borders(Borders::ALL))
             .style(Style::default().bg(GameColor::Black))
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true });
         f.render_widget(paragraph, f.size());
+        render_cache.invalidate();
+        None
     } else {
         let size = f.size();
 
@@ -240,38 +1245,780 @@ fn ui<B: Backend>(f: &mut Frame<B>, game: &Game) {
             .direction(Direction::Horizontal)
             .constraints(
                 [
-                    Constraint::Length(size.width.saturating_sub(SCREEN_WIDTH) / 2),
+                    Constraint::Length(size.width.saturating_sub(screen_width) / 2),
                     Constraint::Length(width),
-                    Constraint::Length(size.width.saturating_sub(SCREEN_WIDTH) / 2),
+                    Constraint::Length(size.width.saturating_sub(screen_width) / 2),
                 ]
                 .as_ref(),
             )
             .split(size);
+        let horizontal_margins = [chunks[0], chunks[2]];
+
+        let message_at_top = game
+            .get_message()
+            .as_ref()
+            .is_some_and(|msg| msg.position == MessagePosition::Top);
+        let (main_chunk, msg_chunk) = if message_at_top { (2, 1) } else { (1, 2) };
+        let status_chunk = 3;
+        let mut constraints =
+            [Constraint::Length(size.height.saturating_sub(screen_height) / 2); 5];
+        constraints[main_chunk] = Constraint::Length(main_height);
+        constraints[msg_chunk] = Constraint::Length(msg_height);
+        constraints[status_chunk] = Constraint::Length(game.status_rows());
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Length(size.height.saturating_sub(SCREEN_HEIGHT) / 2),
-                    Constraint::Length(main_height),
-                    Constraint::Length(msg_height),
-                    Constraint::Length(size.height.saturating_sub(SCREEN_HEIGHT) / 2),
-                ]
-                .as_ref(),
-            )
+            .constraints(constraints.as_ref())
             .split(chunks[1]);
 
-        let charview = CharView::new(game.chunks)
-            .viewport(game.get_viewport())
-            .block(Block::default().borders(Borders::ALL));
-        f.render_widget(charview, chunks[1]);
+        if let Some(margin_color) = settings.margin_color {
+            let margins = horizontal_margins.into_iter().chain([chunks[0], chunks[4]]);
+            for margin in margins {
+                f.render_widget(
+                    Block::default().style(Style::default().bg(margin_color)),
+                    margin,
+                );
+            }
+        }
+
+        let block = game.game_block();
+        let view_area = block.inner(chunks[main_chunk]);
+        f.render_widget(block, chunks[main_chunk]);
+        let cached = render_cache.update(game, view_area);
+        f.render_widget(CachedView(cached), view_area);
+
+        let flashes = game
+            .flashing
+            .iter()
+            .filter_map(|(&(x, y), (style, _))| {
+                let (col, row) = game.world_to_screen(x, y, view_area)?;
+                Some((col, row, Style::from(style.clone())))
+            })
+            .collect();
+        f.render_widget(Flashes(flashes), view_area);
+
+        if let (Some(spacing), false) = (settings.debug_grid, game.is_half_blocks()) {
+            let grid = DebugGrid {
+                viewport: game.drawn_viewport(),
+                spacing,
+            };
+            f.render_widget(grid, view_area);
+        }
+
+        for overlay in &game.overlays {
+            let area = Rect {
+                x: view_area.x.saturating_add(overlay.area.x),
+                y: view_area.y.saturating_add(overlay.area.y),
+                ..overlay.area
+            };
+            if let Some(area) = clip(area, view_area) {
+                f.render_widget(overlay, area);
+            }
+        }
+
+        if let Some((area, scale)) = game.minimap {
+            let area = Rect {
+                x: view_area.x.saturating_add(area.x),
+                y: view_area.y.saturating_add(area.y),
+                ..area
+            };
+            let minimap = Minimap::new(game.chunks)
+                .block(Block::default().borders(Borders::ALL))
+                .scale(scale)
+                .mark(game.get_viewport(), game.view_size());
+            if let Some(area) = clip(area, view_area) {
+                f.render_widget(minimap, area);
+            }
+        }
+
+        for (anchor, offset, text) in &game.hud {
+            let width = u16::try_from(text.chars().count()).unwrap_or(u16::MAX);
+            let (x, y) = anchor.position(view_area, width, *offset);
+            f.render_widget(HudText { x, y, text }, view_area);
+        }
+
+        let cursor = game.get_cursor();
+        if let Some((col, row)) = cursor.and_then(|(x, y)| game.world_to_screen(x, y, view_area)) {
+            f.set_cursor(col, row);
+        }
 
         if let Some(msg) = game.get_message() {
+            let style = Style::default()
+                .bg(msg.background_color.unwrap_or(GameColor::White))
+                .fg(msg.text_color.unwrap_or(GameColor::Black));
             let paragraph = Paragraph::new(msg.text.clone().replace('\t', "  "))
-                .style(Style::default().bg(GameColor::White).fg(GameColor::Black))
-                .block(create_block(msg.title.clone()))
-                .alignment(Alignment::Left);
-            f.render_widget(paragraph, chunks[2]);
+                .style(style)
+                .block(create_block(
+                    msg.title.clone(),
+                    settings.name.clone(),
+                    style,
+                ))
+                .scroll((game.message_scroll, 0))
+                .alignment(msg.alignment);
+            f.render_widget(paragraph, chunks[msg_chunk]);
+        }
+
+        if let Some(status) = game.get_status() {
+            let style = Style::default().bg(GameColor::White).fg(GameColor::Black);
+            f.render_widget(Paragraph::new(status).style(style), chunks[status_chunk]);
+        }
+
+        if settings.show_fps {
+            let text = format!(
+                " {:.1} fps | tick {:.1}ms ",
+                frame_stats.fps(),
+                frame_stats.average_tick_time().as_secs_f64() * 1000.0
+            );
+            let width = (text.len() as u16).min(size.width);
+            let area = Rect::new(size.width - width, 0, width, 1);
+            let style = Style::default().bg(GameColor::White).fg(GameColor::Black);
+            f.render_widget(Paragraph::new(text).style(style), area);
+        }
+
+        Some(view_area)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        render_to_string, run_and_restore, run_game_headless, run_game_with_backend, Anchor,
+        CharChunkMap, Controller, Game, GameColor, GameEvent, GameSettings, GameStyle, KeyCode,
+        ManualClock, Message, SimpleEvent, StyledCharacter,
+    };
+    use std::{
+        io,
+        panic::{self, AssertUnwindSafe},
+        time::Duration,
+    };
+    use tui::{
+        backend::{Backend, TestBackend},
+        buffer::{Buffer, Cell},
+        layout::Rect,
+        style::Modifier,
+        widgets::Paragraph,
+        Terminal,
+    };
+    use unicode_width::UnicodeWidthStr;
+
+    /// A [`TestBackend`] which also records the commands written to it.
+    struct RecordingBackend {
+        inner: TestBackend,
+        written: Vec<u8>,
+    }
+
+    impl Backend for RecordingBackend {
+        fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+        where
+            I: Iterator<Item = (u16, u16, &'a Cell)>,
+        {
+            self.inner.draw(content)
+        }
+        fn hide_cursor(&mut self) -> io::Result<()> {
+            self.inner.hide_cursor()
+        }
+        fn show_cursor(&mut self) -> io::Result<()> {
+            self.inner.show_cursor()
+        }
+        fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+            self.inner.get_cursor()
+        }
+        fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+            self.inner.set_cursor(x, y)
+        }
+        fn clear(&mut self) -> io::Result<()> {
+            self.inner.clear()
+        }
+        fn size(&self) -> io::Result<Rect> {
+            self.inner.size()
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Backend::flush(&mut self.inner)
+        }
+    }
+
+    impl io::Write for RecordingBackend {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct PanicsOnStart;
+
+    impl Controller for PanicsOnStart {
+        fn on_start(&mut self, _game: &mut Game) {
+            panic!("controller panicked");
+        }
+    }
+
+    #[test]
+    fn terminal_restored_after_panic() {
+        let backend = RecordingBackend {
+            inner: TestBackend::new(80, 24),
+            written: Vec::new(),
+        };
+        let mut terminal = Terminal::new(backend).unwrap();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            run_and_restore(
+                &mut terminal,
+                &mut PanicsOnStart,
+                GameSettings::new(),
+                &mut CharChunkMap::new(),
+            )
+        }));
+        assert!(result.is_err());
+
+        let written = String::from_utf8_lossy(&terminal.backend().written).to_string();
+        // LeaveAlternateScreen and DisableMouseCapture respectively.
+        assert!(written.contains("\x1b[?1049l"));
+        assert!(written.contains("\x1b[?1000l"));
+    }
+
+    #[derive(Default)]
+    struct CountsTicks {
+        ticks: u32,
+    }
+
+    impl Controller for CountsTicks {
+        fn on_event(&mut self, game: &mut Game, event: GameEvent) {
+            if let SimpleEvent::Just(KeyCode::Char(ch)) = event.into() {
+                game.set_screen_char(2, 3, Some(StyledCharacter::new(ch)));
+            }
+        }
+        fn on_tick(&mut self, _game: &mut Game) {
+            self.ticks += 1;
+        }
+    }
+
+    #[derive(Default)]
+    struct CountsEvents {
+        events: u32,
+    }
+
+    impl Controller for CountsEvents {
+        fn on_event(&mut self, _game: &mut Game, _event: GameEvent) {
+            self.events += 1;
+        }
+    }
+
+    /// Runs `controller` on an 80x24 screen with an empty map, giving it
+    /// `events` and then running `ticks` ticks. Returns the final screen.
+    fn run_headless(
+        controller: &mut dyn Controller,
+        settings: GameSettings,
+        events: Vec<GameEvent>,
+        ticks: u64,
+    ) -> Buffer {
+        let mut chunks = CharChunkMap::new();
+        run_game_headless(controller, settings, 80, 24, &mut chunks, events, ticks).unwrap()
+    }
+
+    #[test]
+    fn headless_game_runs() {
+        let mut chunks = CharChunkMap::new();
+        let mut controller = CountsTicks::default();
+        let buffer = run_game_headless(
+            &mut controller,
+            GameSettings::new(),
+            80,
+            24,
+            &mut chunks,
+            vec![SimpleEvent::Just(KeyCode::Char('q')).into()],
+            5,
+        )
+        .unwrap();
+
+        assert_eq!(controller.ticks, 5);
+        assert_eq!(chunks.get(2, 3).map(|c| c.c), Some('q'));
+        assert!(buffer.content().iter().any(|cell| cell.symbol == "q"));
+    }
+
+    #[test]
+    fn settings_can_be_sent_to_another_thread() {
+        let (_sender, receiver) = std::sync::mpsc::channel();
+        let settings = GameSettings::new()
+            .quit_when(Box::new(|_| false))
+            .event_source(receiver)
+            .clock(ManualClock::new());
+        let ticks = std::thread::spawn(move || {
+            let mut controller = CountsTicks::default();
+            run_headless(&mut controller, settings, vec![], 3);
+            controller.ticks
+        });
+        assert_eq!(ticks.join().unwrap(), 3);
+    }
+
+    #[test]
+    fn modal_is_dismissed_by_key() {
+        #[derive(Default)]
+        struct ShowsModal {
+            ticks: u32,
+            events: u32,
+            closed: bool,
+            message_when_closed: bool,
+        }
+        impl Controller for ShowsModal {
+            fn on_start(&mut self, game: &mut Game) {
+                game.show_modal(Message::new(String::from("Press any key")));
+            }
+            fn on_event(&mut self, _game: &mut Game, _event: GameEvent) {
+                self.events += 1;
+            }
+            fn on_tick(&mut self, _game: &mut Game) {
+                self.ticks += 1;
+            }
+            fn on_modal_closed(&mut self, game: &mut Game) {
+                self.closed = true;
+                self.message_when_closed = game.get_message().is_some();
+            }
+        }
+
+        let mut controller = ShowsModal::default();
+        let key: GameEvent = SimpleEvent::Just(KeyCode::Char(' ')).into();
+        run_headless(
+            &mut controller,
+            GameSettings::new(),
+            vec![key.clone(), key],
+            3,
+        );
+        assert!(controller.closed);
+        assert!(!controller.message_when_closed);
+        assert_eq!(controller.events, 1);
+        assert_eq!(controller.ticks, 3);
+    }
+
+    #[test]
+    fn paused_game_does_not_tick() {
+        struct PausesOnStart(u32);
+        impl Controller for PausesOnStart {
+            fn on_start(&mut self, game: &mut Game) {
+                game.set_paused(true);
+            }
+            fn on_event(&mut self, game: &mut Game, _event: GameEvent) {
+                game.set_paused(false);
+            }
+            fn on_tick(&mut self, _game: &mut Game) {
+                self.0 += 1;
+            }
+        }
+
+        let mut controller = PausesOnStart(0);
+        run_headless(&mut controller, GameSettings::new(), vec![], 5);
+        assert_eq!(controller.0, 0);
+
+        let unpause = vec![SimpleEvent::Just(KeyCode::Char('p')).into()];
+        run_headless(&mut controller, GameSettings::new(), unpause, 5);
+        assert_eq!(controller.0, 5);
+    }
+
+    #[test]
+    fn cursor_is_shown_in_game_area() {
+        struct ShowsCursor;
+        impl Controller for ShowsCursor {
+            fn on_start(&mut self, game: &mut Game) {
+                game.set_cursor(Some((3, 2)));
+            }
+        }
+
+        // The cursor isn't part of the screen run_headless returns.
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        run_game_with_backend(
+            &mut ShowsCursor,
+            GameSettings::new(),
+            &mut terminal,
+            &mut CharChunkMap::new(),
+            vec![],
+            1,
+        )
+        .unwrap();
+        // One cell in from the border on each side.
+        assert_eq!(terminal.get_cursor().unwrap(), (4, 3));
+    }
+
+    #[test]
+    fn overlays_are_drawn_relative_to_game_area() {
+        struct ShowsOverlay;
+        impl Controller for ShowsOverlay {
+            fn on_start(&mut self, game: &mut Game) {
+                let text = Paragraph::new("hi");
+                game.set_overlay("text", Rect::new(2, 1, 10, 1), text);
+                game.set_overlay("gone", Rect::new(0, 0, 10, 1), Paragraph::new("x"));
+                // Overlays entirely outside the game area aren't drawn.
+                let off_screen = Paragraph::new("far away");
+                game.set_overlay("off screen", Rect::new(100, 30, 10, 1), off_screen);
+                let edge = Paragraph::new("edge");
+                game.set_overlay("edge", Rect::new(u16::MAX - 1, 0, 10, 1), edge);
+                game.remove_overlay("gone");
+            }
+        }
+
+        let buffer = run_headless(&mut ShowsOverlay, GameSettings::new(), vec![], 1);
+        assert_eq!(buffer.get(3, 2).symbol, "h");
+        assert_eq!(buffer.get(4, 2).symbol, "i");
+        assert_eq!(buffer.get(1, 1).symbol, " ");
+    }
+
+    #[test]
+    fn debug_grid_is_drawn_in_empty_cells() {
+        struct DrawsOnGrid;
+        impl Controller for DrawsOnGrid {
+            fn on_start(&mut self, game: &mut Game) {
+                game.set_screen_char(5, 0, Some(StyledCharacter::new('x')));
+            }
+        }
+
+        let mut chunks = CharChunkMap::new();
+        let buffer = run_game_headless(
+            &mut DrawsOnGrid,
+            GameSettings::new().debug_grid(Some(5)),
+            80,
+            24,
+            &mut chunks,
+            vec![],
+            1,
+        )
+        .unwrap();
+        assert_eq!(buffer.get(1, 1).symbol, "+");
+        assert_eq!(buffer.get(6, 1).symbol, "x");
+        assert_eq!(buffer.get(11, 6).symbol, "+");
+        assert_eq!(buffer.get(2, 1).symbol, " ");
+        assert_eq!(buffer.get(1, 2).symbol, " ");
+        assert!(chunks.get(0, 0).is_none());
+    }
+
+    #[test]
+    fn game_ends_at_tick_and_time_limits() {
+        let run = |settings: GameSettings| {
+            let mut controller = CountsTicks::default();
+            let settings = settings.tick_duration(Duration::from_millis(100));
+            run_headless(&mut controller, settings, vec![], 10);
+            controller.ticks
+        };
+
+        assert_eq!(run(GameSettings::new()), 10);
+        assert_eq!(run(GameSettings::new().max_ticks(Some(4))), 4);
+        assert_eq!(run(GameSettings::new().max_ticks(Some(40))), 10);
+        assert_eq!(
+            run(GameSettings::new().time_limit(Some(Duration::from_millis(350)))),
+            3
+        );
+    }
+
+    #[test]
+    fn status_line_is_below_message() {
+        struct ShowsStatus;
+        impl Controller for ShowsStatus {
+            fn on_start(&mut self, game: &mut Game) {
+                game.set_status(Some(String::from("Score: 10")));
+                game.set_message(Some(Message::new(String::from("Hello"))));
+            }
+        }
+
+        let buffer = run_headless(&mut ShowsStatus, GameSettings::new(), vec![], 1);
+        let row = |y: u16| {
+            (0..80)
+                .map(|x| buffer.get(x, y).symbol.clone())
+                .collect::<String>()
+        };
+        assert!(row(23).starts_with("Score: 10"));
+        assert!(row(21).contains("Hello"));
+    }
+
+    #[test]
+    fn flash_is_drawn_for_some_ticks() {
+        #[derive(Default)]
+        struct FlashesOnStart {
+            flashing: Vec<bool>,
+        }
+        impl Controller for FlashesOnStart {
+            fn on_start(&mut self, game: &mut Game) {
+                game.set_screen_char(0, 0, Some(StyledCharacter::new('x')));
+                let style = GameStyle::new().background_color(Some(GameColor::Red));
+                game.flash(0, 0, style, 2);
+            }
+            fn on_tick(&mut self, game: &mut Game) {
+                self.flashing.push(game.flashing.contains_key(&(0, 0)));
+            }
+        }
+
+        let buffer = run_headless(
+            &mut FlashesOnStart::default(),
+            GameSettings::new(),
+            vec![],
+            1,
+        );
+        let cell = buffer.get(1, 1);
+        assert_eq!((cell.symbol.as_str(), cell.bg), ("x", GameColor::Red));
+
+        let mut controller = FlashesOnStart::default();
+        let buffer = run_headless(&mut controller, GameSettings::new(), vec![], 3);
+        // A 2-tick flash is still showing after the first tick, but not the second.
+        assert_eq!(controller.flashing, [true, false, false]);
+        assert_eq!(buffer.get(1, 1).bg, GameColor::Reset);
+    }
+
+    #[test]
+    fn on_render_runs_before_each_frame() {
+        #[derive(Default)]
+        struct CountsRenders {
+            renders: u32,
+        }
+        impl Controller for CountsRenders {
+            fn on_render(&mut self, game: &mut Game) {
+                self.renders += 1;
+                let c = char::from_digit(self.renders, 10).unwrap();
+                game.set_screen_char(0, 0, Some(StyledCharacter::new(c)));
+            }
+        }
+
+        let mut controller = CountsRenders::default();
+        let settings = GameSettings::new()
+            .tick_duration(Duration::from_millis(100))
+            .render_duration(Some(Duration::from_millis(25)));
+        let buffer = run_headless(&mut controller, settings, vec![], 2);
+        // One frame at the start, then one every 25ms for 200ms; but the
+        // last of those is replaced by the final frame, after on_end.
+        assert_eq!(controller.renders, 9);
+        assert_eq!(buffer.get(1, 1).symbol, "9");
+    }
+
+    #[test]
+    fn prompt_collects_typed_text() {
+        #[derive(Default)]
+        struct AsksName {
+            name: Option<String>,
+            other_events: u32,
         }
+        impl Controller for AsksName {
+            fn on_start(&mut self, game: &mut Game) {
+                game.prompt(String::from("Name?"));
+            }
+            fn on_event(&mut self, _game: &mut Game, _event: GameEvent) {
+                self.other_events += 1;
+            }
+            fn on_input_complete(&mut self, _game: &mut Game, text: String) {
+                self.name = Some(text);
+            }
+        }
+
+        let key = |code| SimpleEvent::Just(code).into();
+        let events = vec![
+            key(KeyCode::Char('B')),
+            key(KeyCode::Char('x')),
+            key(KeyCode::Backspace),
+            key(KeyCode::Char('o')),
+            key(KeyCode::Char('b')),
+            key(KeyCode::Enter),
+            key(KeyCode::Char('z')),
+        ];
+        let mut controller = AsksName::default();
+        run_headless(&mut controller, GameSettings::new(), events, 1);
+        assert_eq!(controller.name.as_deref(), Some("Bob"));
+        assert_eq!(controller.other_events, 1);
+    }
+
+    #[test]
+    fn key_repeat_repeats_last_press() {
+        let mut controller = CountsEvents::default();
+        let settings = GameSettings::new()
+            .tick_duration(Duration::from_millis(100))
+            .key_repeat(Some(Duration::from_millis(30)));
+        let events = vec![SimpleEvent::Just(KeyCode::Right).into()];
+        run_headless(&mut controller, settings, events, 3);
+        // Pressed once, then repeated every 30ms for 300ms.
+        assert_eq!(controller.events, 11);
+    }
+
+    #[test]
+    fn paused_ticks_count_towards_limit() {
+        struct PausedGame(u32);
+        impl Controller for PausedGame {
+            fn on_start(&mut self, game: &mut Game) {
+                game.set_paused(true);
+            }
+            fn on_tick(&mut self, _game: &mut Game) {
+                self.0 += 1;
+            }
+        }
+
+        let mut controller = PausedGame(0);
+        run_headless(&mut controller, GameSettings::new(), vec![], 5);
+        assert_eq!(controller.0, 0);
+    }
+
+    #[test]
+    fn key_repeat_stops_without_terminal_repeats() {
+        let run = |settings: GameSettings| {
+            let mut controller = CountsEvents::default();
+            let settings = settings
+                .tick_duration(Duration::from_millis(100))
+                .key_repeat(Some(Duration::from_millis(30)));
+            let events = vec![SimpleEvent::Just(KeyCode::Right).into()];
+            run_headless(&mut controller, settings, events, 20);
+            controller.events
+        };
+        // Pressed once, then repeated every 30ms for 600ms (or 200ms).
+        assert_eq!(run(GameSettings::new()), 20);
+        let tracked = GameSettings::new().track_held_keys(Some(Duration::from_millis(200)));
+        assert_eq!(run(tracked), 7);
+    }
+
+    #[test]
+    fn too_small_terminal_shows_message_or_part_of_game() {
+        struct DrawsPlayer;
+        impl Controller for DrawsPlayer {
+            fn on_start(&mut self, game: &mut Game) {
+                game.set_screen_char(0, 0, Some(StyledCharacter::new('@')));
+            }
+        }
+
+        let run = |settings: GameSettings| {
+            let mut chunks = CharChunkMap::new();
+            let buffer =
+                run_game_headless(&mut DrawsPlayer, settings, 40, 10, &mut chunks, vec![], 1)
+                    .unwrap();
+            (1..39)
+                .map(|x| buffer.get(x, 1).symbol.clone())
+                .collect::<String>()
+        };
+
+        assert!(run(GameSettings::new()).contains("This game needs"));
+        let message = Some(String::from("Too small!"));
+        assert!(run(GameSettings::new().too_small_message(message)).contains("Too small!"));
+        assert!(run(GameSettings::new().draw_when_too_small(true)).starts_with('@'));
+    }
+
+    #[test]
+    fn too_small_message_gives_columns_then_rows() {
+        let mut chunks = CharChunkMap::new();
+        let game = Game::new(&mut chunks);
+        let screen = render_to_string(&game, 100, 10);
+        let row = screen
+            .lines()
+            .nth(1)
+            .unwrap()
+            .trim_matches(|c| c == '│' || c == ' ');
+        assert_eq!(
+            row,
+            "This game needs a terminal of at least 80 columns × 24 rows!"
+        );
+    }
+
+    #[test]
+    fn name_is_used_in_messages() {
+        struct ShowsMessage;
+        impl Controller for ShowsMessage {
+            fn on_start(&mut self, game: &mut Game) {
+                game.set_message(Some(Message::new(String::from("Hello"))));
+            }
+        }
+
+        let run = |width| {
+            let settings = GameSettings::new().name(String::from("Dungeon"));
+            let mut chunks = CharChunkMap::new();
+            let buffer = run_game_headless(
+                &mut ShowsMessage,
+                settings,
+                width,
+                24,
+                &mut chunks,
+                vec![],
+                1,
+            )
+            .unwrap();
+            (0..buffer.area.height)
+                .map(|y| {
+                    (0..width)
+                        .map(|x| buffer.get(x, y).symbol.clone())
+                        .collect()
+                })
+                .collect::<Vec<String>>()
+        };
+
+        let rows = run(80);
+        assert!(rows.iter().any(|row| row.starts_with("┌Dungeon─")));
+        let rows = run(79);
+        assert!(rows[0].starts_with("┌Dungeon: Error─"));
+        assert!(rows[1].contains("Dungeon needs a terminal"));
+    }
+
+    #[test]
+    fn render_to_string_shows_wide_characters_once() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_screen_char(0, 0, Some(StyledCharacter::new('世')));
+        game.set_screen_char(2, 0, Some(StyledCharacter::new('a')));
+        let screen = render_to_string(&game, 80, 24);
+        let row = screen.lines().nth(1).unwrap();
+        assert!(row.starts_with("│世a "));
+        assert_eq!(row.width(), 80);
+    }
+
+    #[test]
+    fn minimap_shows_viewport() {
+        struct ShowsMinimap;
+        impl Controller for ShowsMinimap {
+            fn on_start(&mut self, game: &mut Game) {
+                game.set_screen_char(7, 3, Some(StyledCharacter::new('@')));
+                game.draw_minimap(Rect::new(0, 0, 22, 12), 8);
+            }
+        }
+
+        let buffer = run_headless(&mut ShowsMinimap, GameSettings::new(), vec![], 1);
+        // The minimap's border is inside the game's border.
+        assert_eq!(buffer.get(1, 1).symbol, "┌");
+        // The inside of the minimap starts at (-41, -29) on the map, and
+        // the 78x22 viewport covers the 10x4 cells starting at (5, 3).
+        let (minimap_x, minimap_y) = (2, 2);
+        assert_eq!(buffer.get(minimap_x + 6, minimap_y + 4).symbol, "@");
+        let reversed = |x, y| buffer.get(x, y).modifier.contains(Modifier::REVERSED);
+        assert!(reversed(minimap_x + 5, minimap_y + 3));
+        assert!(reversed(minimap_x + 14, minimap_y + 6));
+        assert!(!reversed(minimap_x + 4, minimap_y + 3));
+        assert!(!reversed(minimap_x + 15, minimap_y + 6));
+        assert!(!reversed(minimap_x + 5, minimap_y + 7));
+    }
+
+    #[test]
+    fn minimap_outside_game_area_is_not_drawn() {
+        struct ShowsMinimap;
+        impl Controller for ShowsMinimap {
+            fn on_start(&mut self, game: &mut Game) {
+                game.draw_minimap(Rect::new(0, 30, 10, 5), 4);
+            }
+        }
+
+        run_headless(&mut ShowsMinimap, GameSettings::new(), vec![], 1);
+    }
+
+    #[test]
+    fn hud_text_stays_put_when_viewport_moves() {
+        struct ShowsScore;
+        impl Controller for ShowsScore {
+            fn on_start(&mut self, game: &mut Game) {
+                game.draw_hud_text(Anchor::TopRight, (0, 0), "old");
+                game.draw_hud_text(Anchor::TopRight, (0, 0), "9");
+                game.draw_hud_text(Anchor::BottomLeft, (-1, 0), "ab");
+                game.move_viewport(50, 50);
+            }
+        }
+
+        let buffer = run_headless(&mut ShowsScore, GameSettings::new(), vec![], 1);
+        assert_eq!(buffer.get(78, 1).symbol, "9");
+        assert_eq!(buffer.get(77, 1).symbol, " ");
+        // The "a" is cut off by the border.
+        assert_eq!(buffer.get(0, 22).symbol, "│");
+        assert_eq!(buffer.get(1, 22).symbol, "b");
+    }
+
+    #[test]
+    fn headless_game_quits_on_quit_event() {
+        let mut controller = CountsTicks::default();
+        let quit = vec![SimpleEvent::WithControl(KeyCode::Char('c')).into()];
+        run_headless(&mut controller, GameSettings::new(), quit, 5);
+        assert_eq!(controller.ticks, 0);
     }
 }
@@ -1,3 +1,16 @@
+use std::time::Duration;
+use tui::{layout::Alignment, style::Color as GameColor};
+
+/// Where a [`Message`] is shown, relative to the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessagePosition {
+    /// The message is shown above the game.
+    Top,
+    /// The message is shown below the game.
+    #[default]
+    Bottom,
+}
+
 /// The [`Message`] struct is used when displaying
 /// a message to a user.
 pub struct Message {
@@ -8,12 +21,40 @@ pub struct Message {
 
     /// This is the main body of the message.
     pub text: String,
+
+    /// If present, the message will be removed automatically
+    /// once it has been shown for this long.
+    pub timeout: Option<Duration>,
+
+    /// This decides whether the message is shown above
+    /// or below the game. By default, it is below.
+    pub position: MessagePosition,
+
+    /// The color of the message's text and border.
+    /// If not present, it is black.
+    pub text_color: Option<GameColor>,
+
+    /// The color behind the message.
+    /// If not present, it is white.
+    pub background_color: Option<GameColor>,
+
+    /// How each line of the message is lined up.
+    /// By default, it is left-aligned.
+    pub alignment: Alignment,
 }
 
 impl Message {
     /// Creates a message with the given text.
     pub fn new(text: String) -> Message {
-        Message { title: None, text }
+        Message {
+            title: None,
+            text,
+            timeout: None,
+            position: MessagePosition::Bottom,
+            text_color: None,
+            background_color: None,
+            alignment: Alignment::Left,
+        }
     }
 
     /// Builder method to add a title to an existing Message.
@@ -27,4 +68,68 @@ impl Message {
         self.title = Some(title);
         self
     }
+
+    /// Builder method to remove the message automatically, once
+    /// it has been shown for the given [`Duration`].
+    ///
+    /// ```rust
+    /// use termgame::Message;
+    /// use std::time::Duration;
+    /// Message::new(String::from("You found a key!"))
+    ///          .timeout(Duration::from_secs(2));
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Message {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Builder method to choose where the message is shown.
+    pub fn position(mut self, position: MessagePosition) -> Message {
+        self.position = position;
+        self
+    }
+
+    /// Builder method to show the message above the game,
+    /// rather than below it.
+    ///
+    /// ```rust
+    /// use termgame::Message;
+    /// Message::new(String::from("Long ago, in a distant land..."))
+    ///          .at_top();
+    /// ```
+    pub fn at_top(self) -> Message {
+        self.position(MessagePosition::Top)
+    }
+
+    /// Builder method to set the color of the message's text and border.
+    ///
+    /// ```rust
+    /// use termgame::{GameColor, Message};
+    /// Message::new(String::from("Something is behind you."))
+    ///          .text_color(GameColor::Red)
+    ///          .background_color(GameColor::Black);
+    /// ```
+    pub fn text_color(mut self, text_color: GameColor) -> Message {
+        self.text_color = Some(text_color);
+        self
+    }
+
+    /// Builder method to set the color behind the message.
+    pub fn background_color(mut self, background_color: GameColor) -> Message {
+        self.background_color = Some(background_color);
+        self
+    }
+
+    /// Builder method to line up the message's text on the left (the
+    /// default), in the center, or on the right.
+    ///
+    /// ```rust
+    /// use termgame::{Alignment, Message};
+    /// Message::new(String::from("GAME OVER"))
+    ///          .alignment(Alignment::Center);
+    /// ```
+    pub fn alignment(mut self, alignment: Alignment) -> Message {
+        self.alignment = alignment;
+        self
+    }
 }
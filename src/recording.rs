@@ -0,0 +1,304 @@
+use crate::event_source::EventSource;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Keys which are written by name in a recording.
+const NAMED_KEYS: [(KeyCode, &str); 23] = [
+    (KeyCode::Backspace, "backspace"),
+    (KeyCode::Enter, "enter"),
+    (KeyCode::Left, "left"),
+    (KeyCode::Right, "right"),
+    (KeyCode::Up, "up"),
+    (KeyCode::Down, "down"),
+    (KeyCode::Home, "home"),
+    (KeyCode::End, "end"),
+    (KeyCode::PageUp, "page-up"),
+    (KeyCode::PageDown, "page-down"),
+    (KeyCode::Tab, "tab"),
+    (KeyCode::BackTab, "back-tab"),
+    (KeyCode::Delete, "delete"),
+    (KeyCode::Insert, "insert"),
+    (KeyCode::Null, "null"),
+    (KeyCode::Esc, "esc"),
+    (KeyCode::CapsLock, "caps-lock"),
+    (KeyCode::ScrollLock, "scroll-lock"),
+    (KeyCode::NumLock, "num-lock"),
+    (KeyCode::PrintScreen, "print-screen"),
+    (KeyCode::Pause, "pause"),
+    (KeyCode::Menu, "menu"),
+    (KeyCode::KeypadBegin, "keypad-begin"),
+];
+
+/// Mouse buttons, by the name they are written with in a recording.
+const MOUSE_BUTTONS: [(MouseButton, &str); 3] = [
+    (MouseButton::Left, "left"),
+    (MouseButton::Right, "right"),
+    (MouseButton::Middle, "middle"),
+];
+
+/// Write one line of a recording: the number of milliseconds since the game
+/// started, followed by the event. Events which can't be written
+/// (pastes, and media or modifier keys) are skipped.
+pub(crate) fn write_event(
+    writer: &mut dyn Write,
+    elapsed: Duration,
+    event: &Event,
+) -> io::Result<()> {
+    match encode_event(event) {
+        Some(encoded) => writeln!(writer, "{} {}", elapsed.as_millis(), encoded),
+        None => Ok(()),
+    }
+}
+
+/// Read every line of a recording made with [`write_event`].
+pub(crate) fn read_events(reader: impl BufRead) -> io::Result<Vec<(Duration, Event)>> {
+    let mut events = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (millis, event) = line
+            .split_once(' ')
+            .and_then(|(millis, event)| Some((millis.parse().ok()?, decode_event(event)?)))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid event on line {} of recording", number + 1),
+                )
+            })?;
+        events.push((Duration::from_millis(millis), event));
+    }
+    Ok(events)
+}
+
+fn encode_event(event: &Event) -> Option<String> {
+    Some(match event {
+        Event::FocusGained => "focus-gained".to_string(),
+        Event::FocusLost => "focus-lost".to_string(),
+        Event::Resize(width, height) => format!("resize {width} {height}"),
+        Event::Key(key) => {
+            let code = match key.code {
+                KeyCode::Char(ch) => format!("char:{}", ch as u32),
+                KeyCode::F(n) => format!("f:{n}"),
+                code => NAMED_KEYS
+                    .iter()
+                    .find(|(named, _)| *named == code)?
+                    .1
+                    .to_string(),
+            };
+            let kind = match key.kind {
+                KeyEventKind::Press => "press",
+                KeyEventKind::Repeat => "repeat",
+                KeyEventKind::Release => "release",
+            };
+            format!(
+                "key {code} {} {kind} {}",
+                key.modifiers.bits(),
+                key.state.bits()
+            )
+        }
+        Event::Mouse(mouse) => {
+            let button_name = |button| MOUSE_BUTTONS.iter().find(|(b, _)| *b == button).unwrap().1;
+            let kind = match mouse.kind {
+                MouseEventKind::Down(button) => format!("down:{}", button_name(button)),
+                MouseEventKind::Up(button) => format!("up:{}", button_name(button)),
+                MouseEventKind::Drag(button) => format!("drag:{}", button_name(button)),
+                MouseEventKind::Moved => "moved".to_string(),
+                MouseEventKind::ScrollDown => "scroll-down".to_string(),
+                MouseEventKind::ScrollUp => "scroll-up".to_string(),
+            };
+            format!(
+                "mouse {kind} {} {} {}",
+                mouse.column,
+                mouse.row,
+                mouse.modifiers.bits()
+            )
+        }
+        Event::Paste(_) => return None,
+    })
+}
+
+fn decode_event(line: &str) -> Option<Event> {
+    let mut words = line.split(' ');
+    let event = match words.next()? {
+        "focus-gained" => Event::FocusGained,
+        "focus-lost" => Event::FocusLost,
+        "resize" => Event::Resize(words.next()?.parse().ok()?, words.next()?.parse().ok()?),
+        "key" => {
+            let code = words.next()?;
+            let code = match code.split_once(':') {
+                Some(("char", ch)) => KeyCode::Char(char::from_u32(ch.parse().ok()?)?),
+                Some(("f", n)) => KeyCode::F(n.parse().ok()?),
+                Some(_) => return None,
+                None => NAMED_KEYS.iter().find(|(_, name)| *name == code)?.0,
+            };
+            let modifiers = KeyModifiers::from_bits(words.next()?.parse().ok()?)?;
+            let kind = match words.next()? {
+                "press" => KeyEventKind::Press,
+                "repeat" => KeyEventKind::Repeat,
+                "release" => KeyEventKind::Release,
+                _ => return None,
+            };
+            let state = KeyEventState::from_bits(words.next()?.parse().ok()?)?;
+            Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind,
+                state,
+            })
+        }
+        "mouse" => {
+            let kind = words.next()?;
+            let button = |name: &str| Some(MOUSE_BUTTONS.iter().find(|(_, b)| *b == name)?.0);
+            let kind = match kind.split_once(':') {
+                Some(("down", name)) => MouseEventKind::Down(button(name)?),
+                Some(("up", name)) => MouseEventKind::Up(button(name)?),
+                Some(("drag", name)) => MouseEventKind::Drag(button(name)?),
+                Some(_) => return None,
+                None => match kind {
+                    "moved" => MouseEventKind::Moved,
+                    "scroll-down" => MouseEventKind::ScrollDown,
+                    "scroll-up" => MouseEventKind::ScrollUp,
+                    _ => return None,
+                },
+            };
+            Event::Mouse(MouseEvent {
+                kind,
+                column: words.next()?.parse().ok()?,
+                row: words.next()?.parse().ok()?,
+                modifiers: KeyModifiers::from_bits(words.next()?.parse().ok()?)?,
+            })
+        }
+        _ => return None,
+    };
+    match words.next() {
+        Some(_) => None,
+        None => Some(event),
+    }
+}
+
+/// An [`EventSource`] which replays a recording made with
+/// [`GameSettings::record_to`](crate::GameSettings::record_to),
+/// giving out each event at the same time it originally happened.
+///
+/// The recording is read the first time an event is asked for, which is
+/// also when the replay starts.
+#[derive(Debug)]
+pub(crate) struct ReplayEvents {
+    path: PathBuf,
+    events: Option<VecDeque<(Duration, Event)>>,
+    started: Instant,
+}
+
+impl ReplayEvents {
+    pub(crate) fn new(path: PathBuf) -> ReplayEvents {
+        ReplayEvents {
+            path,
+            events: None,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl EventSource for ReplayEvents {
+    fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        let events = match &mut self.events {
+            Some(events) => events,
+            None => {
+                let file = BufReader::new(File::open(&self.path)?);
+                self.started = Instant::now();
+                self.events.insert(read_events(file)?.into())
+            }
+        };
+
+        let elapsed = self.started.elapsed();
+        match events.front() {
+            Some((at, _)) if *at <= elapsed => Ok(events.pop_front().map(|(_, event)| event)),
+            Some((at, _)) if *at - elapsed <= timeout => {
+                thread::sleep(*at - elapsed);
+                Ok(events.pop_front().map(|(_, event)| event))
+            }
+            _ => {
+                thread::sleep(timeout);
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_events, write_event};
+    use crate::{KeyCode, MouseEventKind, SimpleEvent};
+    use crossterm::event::{Event, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+    use std::time::Duration;
+
+    #[test]
+    fn events_survive_recording() {
+        let events: Vec<(Duration, Event)> = vec![
+            (
+                Duration::from_millis(0),
+                SimpleEvent::Just(KeyCode::Char(' ')).into(),
+            ),
+            (
+                Duration::from_millis(15),
+                SimpleEvent::WithControlShift(KeyCode::F(4)).into(),
+            ),
+            (
+                Duration::from_millis(20),
+                Event::Key(KeyEvent {
+                    code: KeyCode::PageDown,
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Release,
+                    state: KeyEventState::KEYPAD,
+                }),
+            ),
+            (
+                Duration::from_millis(1200),
+                SimpleEvent::LeftClick { x: 4, y: 9 }.into(),
+            ),
+            (
+                Duration::from_millis(1300),
+                Event::Mouse(crossterm::event::MouseEvent {
+                    kind: MouseEventKind::ScrollUp,
+                    column: 1,
+                    row: 2,
+                    modifiers: KeyModifiers::ALT,
+                }),
+            ),
+            (Duration::from_millis(5000), Event::Resize(100, 30)),
+            (Duration::from_millis(5001), Event::FocusLost),
+        ];
+
+        let mut recording = Vec::new();
+        for (elapsed, event) in &events {
+            write_event(&mut recording, *elapsed, event).unwrap();
+        }
+        write_event(
+            &mut recording,
+            Duration::ZERO,
+            &Event::Paste("skipped".into()),
+        )
+        .unwrap();
+
+        assert_eq!(read_events(&recording[..]).unwrap(), events);
+    }
+
+    #[test]
+    fn invalid_recording_is_an_error() {
+        assert!(read_events(&b"10 key wobble 0 press 0\n"[..]).is_err());
+        assert!(read_events(&b"ten resize 1 2\n"[..]).is_err());
+        assert!(read_events(&b"10 resize 1 2 3\n"[..]).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{CharView, Game, ViewportLocation};
+use std::collections::HashSet;
+use tui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+/// The game's characters as they were last drawn. Drawing every character
+/// means looking each one up in the chunkmap, so instead only the rows
+/// which have changed since the last frame are drawn again, and the rest
+/// are copied from here.
+#[derive(Debug, Default)]
+pub(crate) struct RenderCache {
+    /// If false, nothing in the cache can be reused.
+    valid: bool,
+    buffer: Buffer,
+    viewport: ViewportLocation,
+    hidden: HashSet<(i32, i32)>,
+}
+
+impl RenderCache {
+    /// Makes sure everything is drawn again next time.
+    pub(crate) fn invalidate(&mut self) {
+        self.valid = false;
+    }
+
+    /// Brings the cache up to date with the characters in `game`, drawn in
+    /// `area`, and returns the result. If nothing in the cache can be
+    /// reused (for example, because the viewport moved), everything is
+    /// drawn again.
+    pub(crate) fn update(&mut self, game: &Game, area: Rect) -> &Buffer {
+        let viewport = game.drawn_viewport();
+        let hidden = game.hidden_cells();
+        // Redrawing single rows only works when there is one viewport.
+        if self.valid
+            && !game.all_dirty
+            && game.split.is_none()
+            && self.buffer.area == area
+            && self.viewport == viewport
+        {
+            // Cells which started or stopped blinking have changed too.
+            let blinked = self.hidden.symmetric_difference(&hidden).map(|(_, y)| *y);
+            // In half-block mode, each row of cells shows more than one row.
+            let rows_per_cell = i64::from(game.rows_per_cell());
+            let cell_rows: HashSet<i64> = game
+                .dirty_rows
+                .iter()
+                .copied()
+                .chain(blinked)
+                .map(|row| (i64::from(row) - i64::from(viewport.y)).div_euclid(rows_per_cell))
+                .collect();
+            for cell_row in cell_rows {
+                let y = cell_row + i64::from(area.y);
+                let row = (i64::from(viewport.y) + cell_row * rows_per_cell) as i32;
+                match u16::try_from(y) {
+                    Ok(y) if (area.top()..area.bottom()).contains(&y) => {
+                        let row_area = Rect::new(area.x, y, area.width, 1);
+                        for x in row_area.left()..row_area.right() {
+                            self.buffer.get_mut(x, y).reset();
+                        }
+                        CharView::new(game.chunks)
+                            .viewport(ViewportLocation {
+                                x: viewport.x,
+                                y: row,
+                            })
+                            .hidden(&hidden)
+                            .half_blocks(game.half_blocks)
+                            .render(row_area, &mut self.buffer);
+                    }
+                    _ => {}
+                }
+            }
+        } else {
+            self.buffer = Buffer::empty(area);
+            let mut charview = CharView::new(game.chunks)
+                .viewport(viewport)
+                .hidden(&hidden)
+                .half_blocks(game.half_blocks);
+            if let Some((second_viewport, direction)) = game.split {
+                charview = charview.split(second_viewport, direction);
+            }
+            charview.render(area, &mut self.buffer);
+            self.viewport = viewport;
+            self.valid = true;
+        }
+        self.hidden = hidden;
+        &self.buffer
+    }
+}
+
+/// A widget which copies a [`Buffer`] onto the screen.
+pub(crate) struct CachedView<'a>(pub(crate) &'a Buffer);
+
+impl Widget for CachedView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(self.0.area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                *buf.get_mut(x, y) = self.0.get(x, y).clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderCache;
+    use crate::{CharChunkMap, Game, SplitDirection, StyledCharacter, ViewportLocation};
+    use tui::layout::Rect;
+
+    #[test]
+    fn only_dirty_rows_are_redrawn() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        let area = Rect::new(1, 1, 5, 3);
+        let mut cache = RenderCache::default();
+
+        game.set_screen_char(0, 0, Some(StyledCharacter::new('a')));
+        assert_eq!(cache.update(&game, area).get(1, 1).symbol, "a");
+        game.all_dirty = false;
+        game.dirty_rows.clear();
+
+        // Changing the map behind the game's back isn't noticed...
+        game.chunks.insert(1, 0, StyledCharacter::new('b').into());
+        assert_eq!(cache.update(&game, area).get(2, 1).symbol, " ");
+
+        // ...until that row is drawn again.
+        game.set_screen_char(0, 0, None);
+        let buffer = cache.update(&game, area);
+        assert_eq!(buffer.get(1, 1).symbol, " ");
+        assert_eq!(buffer.get(2, 1).symbol, "b");
+    }
+
+    #[test]
+    fn split_shows_both_viewports() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        let area = Rect::new(0, 0, 10, 2);
+        let mut cache = RenderCache::default();
+        game.set_screen_char(0, 0, Some(StyledCharacter::new('a')));
+        game.set_screen_char(100, 0, Some(StyledCharacter::new('b')));
+        assert_eq!(cache.update(&game, area).get(5, 0).symbol, " ");
+        game.all_dirty = false;
+
+        let second = ViewportLocation { x: 100, y: 0 };
+        game.set_split(Some((second, SplitDirection::Horizontal)));
+        let buffer = cache.update(&game, area);
+        assert_eq!(buffer.get(0, 0).symbol, "a");
+        assert_eq!(buffer.get(5, 0).symbol, "b");
+        game.all_dirty = false;
+
+        game.set_split(None);
+        assert_eq!(cache.update(&game, area).get(5, 0).symbol, " ");
+    }
+
+    #[test]
+    fn half_block_rows_are_redrawn() {
+        let mut chunks = CharChunkMap::new();
+        let mut game = Game::new(&mut chunks);
+        game.set_half_blocks(true);
+        let area = Rect::new(0, 0, 5, 3);
+        let mut cache = RenderCache::default();
+        cache.update(&game, area);
+        game.all_dirty = false;
+
+        // Row 3 of the map is the bottom half of the second row of cells.
+        game.set_screen_char(1, 3, Some(StyledCharacter::new('a')));
+        assert_eq!(cache.update(&game, area).get(1, 1).symbol, "▄");
+    }
+}
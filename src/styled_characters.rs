@@ -1,13 +1,8 @@
 use super::charview::screen_character::ScreenCharacter;
 use tui::style::Style as TuiStyle;
 
-pub use super::charview::{CharChunkMap, ViewportLocation};
 pub use tui::style::{Color as GameColor, Modifier as Font};
 
-pub use crossterm::event::KeyCode as GameEvent;
-
-pub use super::{Message, SCREEN_HEIGHT, SCREEN_WIDTH};
-
 /// This struct models how to show a character in Termgame.
 ///
 /// To use it, you can do the following:
@@ -51,11 +46,59 @@ impl Style {
         self
     }
 
+    /// Apply the color made of the given red, green and blue
+    /// amounts as the foreground.
+    ///
+    /// ```rust
+    /// use termgame::GameStyle;
+    /// let orange = GameStyle::new().color_rgb(255, 165, 0);
+    /// ```
+    pub fn color_rgb(self, r: u8, g: u8, b: u8) -> Style {
+        self.color(Some(GameColor::Rgb(r, g, b)))
+    }
+
+    /// Apply the color made of the given red, green and blue
+    /// amounts as the background.
+    pub fn background_rgb(self, r: u8, g: u8, b: u8) -> Style {
+        self.background_color(Some(GameColor::Rgb(r, g, b)))
+    }
+
     /// Apply the [`Font`] specified.
     pub fn font(mut self, font: Option<Font>) -> Style {
         self.font = font;
         self
     }
+
+    /// Layer `other` on top of this style. Any color set in `other`
+    /// replaces the color in this style, while fonts are combined.
+    ///
+    /// ```rust
+    /// use termgame::{GameStyle, GameColor, Font};
+    /// let tile = GameStyle::new().color(Some(GameColor::Blue));
+    /// let hover = GameStyle::new().font(Some(Font::BOLD));
+    /// assert_eq!(
+    ///     tile.merge(hover),
+    ///     GameStyle::new()
+    ///         .color(Some(GameColor::Blue))
+    ///         .font(Some(Font::BOLD))
+    /// );
+    /// ```
+    pub fn merge(self, other: Style) -> Style {
+        Style {
+            color: other.color.or(self.color),
+            background_color: other.background_color.or(self.background_color),
+            font: match (self.font, other.font) {
+                (Some(a), Some(b)) => Some(a | b),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+}
+
+impl Default for Style {
+    fn default() -> Style {
+        Style::new()
+    }
 }
 
 /// A character with a given style.
@@ -91,28 +134,35 @@ impl StyledCharacter {
     }
 }
 
+/// The default [`StyledCharacter`] is an unstyled space.
+impl Default for StyledCharacter {
+    fn default() -> Self {
+        StyledCharacter::new(' ')
+    }
+}
+
 impl From<char> for StyledCharacter {
     fn from(c: char) -> Self {
         StyledCharacter { c, style: None }
     }
 }
 
+impl From<Style> for TuiStyle {
+    fn from(style: Style) -> Self {
+        TuiStyle {
+            fg: style.color,
+            bg: style.background_color,
+            add_modifier: style.font.unwrap_or(Font::empty()),
+            sub_modifier: Font::empty(),
+        }
+    }
+}
+
 impl From<StyledCharacter> for ScreenCharacter {
     fn from(styled_char: StyledCharacter) -> Self {
-        match styled_char.style {
-            Some(s) => ScreenCharacter {
-                c: styled_char.c,
-                style: Some(TuiStyle {
-                    fg: s.color,
-                    bg: s.background_color,
-                    add_modifier: s.font.unwrap_or(Font::empty()),
-                    sub_modifier: Font::empty(),
-                }),
-            },
-            None => ScreenCharacter {
-                c: styled_char.c,
-                style: None,
-            },
+        ScreenCharacter {
+            c: styled_char.c,
+            style: styled_char.style.map(TuiStyle::from),
         }
     }
 }
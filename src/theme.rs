@@ -0,0 +1,54 @@
+#![warn(missing_docs)]
+
+use crate::styled_characters::{Font, GameColor, Style};
+
+/// A set of ready-made [`Style`]s, chosen to be readable
+/// on most terminals.
+///
+/// ```rust
+/// use termgame::{StyledCharacter, Theme};
+/// StyledCharacter::new('!').style(Theme::warning());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Theme;
+
+impl Theme {
+    /// Bold yellow text, for things the player should be careful of.
+    pub fn warning() -> Style {
+        Style::new()
+            .color(Some(GameColor::Yellow))
+            .font(Some(Font::BOLD))
+    }
+
+    /// Bold white text on a red background, for things that have gone wrong.
+    pub fn danger() -> Style {
+        Style::new()
+            .color(Some(GameColor::White))
+            .background_color(Some(GameColor::Red))
+            .font(Some(Font::BOLD))
+    }
+
+    /// Bold green text, for things that have gone well.
+    pub fn success() -> Style {
+        Style::new()
+            .color(Some(GameColor::Green))
+            .font(Some(Font::BOLD))
+    }
+
+    /// Cyan text, for information the player might want.
+    pub fn info() -> Style {
+        Style::new().color(Some(GameColor::Cyan))
+    }
+
+    /// Dark gray text, for things that are unimportant or in the background.
+    pub fn dim() -> Style {
+        Style::new().color(Some(GameColor::DarkGray))
+    }
+
+    /// Black text on a yellow background, for drawing attention to something.
+    pub fn highlight() -> Style {
+        Style::new()
+            .color(Some(GameColor::Black))
+            .background_color(Some(GameColor::Yellow))
+    }
+}
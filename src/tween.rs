@@ -0,0 +1,157 @@
+//! Helpers for animating things smoothly over time, like a sprite sliding
+//! between two places or a colour fading in.
+//!
+//! Nothing here draws anything; call [`Tween::advance`] from
+//! [`Controller::on_tick`](crate::Controller::on_tick) with
+//! [`Game::delta_time`](crate::Game::delta_time), and use the value it
+//! gives you however you like.
+
+use std::time::Duration;
+
+/// Returns the value `t` of the way from `a` to `b`, rounded to the
+/// nearest whole number. `t` is clamped between `0.0` (which gives `a`)
+/// and `1.0` (which gives `b`).
+///
+/// ```rust
+/// use termgame::lerp;
+///
+/// assert_eq!(lerp(0, 10, 0.5), 5);
+/// assert_eq!(lerp(10, -10, 0.25), 5);
+/// assert_eq!(lerp(0, 10, 2.0), 10);
+/// ```
+pub fn lerp(a: i32, b: i32, t: f32) -> i32 {
+    let distance = (i64::from(b) - i64::from(a)) as f64;
+    let t = f64::from(t.clamp(0.0, 1.0));
+    (i64::from(a) + (distance * t).round() as i64) as i32
+}
+
+/// How a [`Tween`] moves between its start and end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Move at the same speed the whole way.
+    #[default]
+    Linear,
+    /// Start slowly, and speed up towards the end.
+    EaseIn,
+    /// Start quickly, and slow down towards the end.
+    EaseOut,
+    /// Start slowly, speed up, then slow down again at the end.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Changes how far along something is (`t`, from `0.0` to `1.0`)
+    /// into how far along it should appear to be.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut if t < 0.5 => 2.0 * t * t,
+            Easing::EaseInOut => 1.0 - 2.0 * (1.0 - t) * (1.0 - t),
+        }
+    }
+}
+
+/// A value which moves from one number to another over a set amount of
+/// time.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use termgame::{lerp, Tween};
+///
+/// let mut fade = Tween::new(0.0, 1.0, Duration::from_millis(200));
+/// fade.advance(Duration::from_millis(50));
+/// assert_eq!(fade.value(), 0.25);
+///
+/// // To move something between two coordinates, use the progress.
+/// let x = lerp(10, 30, fade.progress());
+/// assert_eq!(x, 15);
+///
+/// fade.advance(Duration::from_millis(500));
+/// assert!(fade.is_done());
+/// assert_eq!(fade.value(), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween {
+    from: f32,
+    to: f32,
+    duration: Duration,
+    elapsed: Duration,
+    easing: Easing,
+}
+
+impl Tween {
+    /// Creates a tween which moves from `from` to `to` over `duration`,
+    /// at a constant speed.
+    pub fn new(from: f32, to: f32, duration: Duration) -> Tween {
+        Tween {
+            from,
+            to,
+            duration,
+            elapsed: Duration::ZERO,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Set how the tween moves between its start and end.
+    pub fn easing(mut self, easing: Easing) -> Tween {
+        self.easing = easing;
+        self
+    }
+
+    /// Moves the tween forward by `by`. Once the tween is done, this does
+    /// nothing.
+    pub fn advance(&mut self, by: Duration) {
+        self.elapsed = self.elapsed.saturating_add(by).min(self.duration);
+    }
+
+    /// Start the tween again from the beginning.
+    pub fn restart(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// How far along the tween is, from `0.0` to `1.0`, after easing.
+    pub fn progress(&self) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let t = self.elapsed.as_secs_f64() / self.duration.as_secs_f64();
+        self.easing.apply(t as f32)
+    }
+
+    /// The current value of the tween, between `from` and `to`.
+    pub fn value(&self) -> f32 {
+        self.from + (self.to - self.from) * self.progress()
+    }
+
+    /// Returns `true` once the tween has reached `to`.
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Easing, Tween};
+    use std::time::Duration;
+
+    #[test]
+    fn eased_tween_reaches_the_end() {
+        let mut tween = Tween::new(10.0, 20.0, Duration::from_secs(2)).easing(Easing::EaseIn);
+        assert_eq!(tween.value(), 10.0);
+        tween.advance(Duration::from_secs(1));
+        assert_eq!(tween.value(), 12.5);
+        assert!(!tween.is_done());
+        tween.advance(Duration::from_secs(5));
+        assert_eq!(tween.value(), 20.0);
+        assert!(tween.is_done());
+        tween.restart();
+        assert_eq!(tween.progress(), 0.0);
+
+        assert_eq!(Easing::EaseInOut.apply(0.25), 0.125);
+        assert_eq!(Easing::EaseOut.apply(0.5), 0.75);
+        assert!(Tween::new(1.0, 2.0, Duration::ZERO).is_done());
+    }
+}